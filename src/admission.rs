@@ -0,0 +1,285 @@
+// File: admission.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Admission control: per-tier concurrency limits with a bounded,
+//     priority-ordered waiting queue that fails fast when full or when
+//     the expected wait exceeds the deadline.
+//
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::oneshot;
+
+use crate::{error::AgentError, models::Tier};
+
+/// Weight of the newest sample in the per-tier hold time estimate.
+const EWMA_ALPHA: f64 = 0.2;
+
+#[derive(Clone, Debug)]
+pub struct AdmissionConfig {
+    /// Requests in flight per tier; tiers without an entry are unlimited.
+    pub tier_limits: BTreeMap<Tier, usize>,
+    /// Requests waiting across all tiers before new ones are refused.
+    pub queue_size: usize,
+    /// Longest a request may wait for a slot.
+    pub max_wait: Duration,
+}
+
+struct Waiter {
+    priority: i32,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+// Higher priority first, then first come first served.
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl Eq for Waiter {}
+
+#[derive(Default)]
+struct TierState {
+    in_flight: usize,
+    waiters: BinaryHeap<Waiter>,
+    /// Average time a slot is held, for wait estimates.
+    hold_ewma_ms: f64,
+}
+
+impl TierState {
+    /// Expected wait for a slot with `ahead` requests (this one included)
+    /// served before it, `limit` at a time.
+    fn wait_estimate_ms(&self, ahead: usize, limit: usize) -> f64 {
+        ahead as f64 * self.hold_ewma_ms / limit.max(1) as f64
+    }
+}
+
+/// `Retry-After` for a refused request: the estimated wait, in whole
+/// seconds and at least one.
+fn retry_after_secs(estimate_ms: f64) -> u64 {
+    (estimate_ms / 1000.0).ceil().max(1.0) as u64
+}
+
+#[derive(Default)]
+struct State {
+    tiers: HashMap<Tier, TierState>,
+    queued: usize,
+    seq: u64,
+}
+
+pub struct Admission {
+    cfg: AdmissionConfig,
+    state: Mutex<State>,
+}
+
+impl Admission {
+    pub fn new(cfg: AdmissionConfig) -> Self {
+        Self {
+            cfg,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Waits for a slot on `tier`. Refuses with `RateLimit` (429) when the
+    /// queue is full and with `NoCapacity` (503) when the estimated or
+    /// actual wait exceeds `max_wait`, either way with the estimated wait
+    /// as the `Retry-After` hint.
+    pub async fn admit(&self, tier: Tier, priority: i32) -> Result<Permit<'_>, AgentError> {
+        let Some(limit) = self.cfg.tier_limits.get(&tier).copied() else {
+            return Ok(Permit {
+                admission: self,
+                tier,
+                since: Instant::now(),
+                limited: false,
+            });
+        };
+        let max_wait_ms = self.cfg.max_wait.as_millis() as f64;
+
+        let (rx, seq) = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let queued = state.queued;
+            let t = state.tiers.entry(tier).or_default();
+            if t.in_flight < limit && t.waiters.is_empty() {
+                t.in_flight += 1;
+                return Ok(Permit {
+                    admission: self,
+                    tier,
+                    since: Instant::now(),
+                    limited: true,
+                });
+            }
+            let ahead = t.waiters.iter().filter(|w| w.priority >= priority).count() + 1;
+            let estimate_ms = t.wait_estimate_ms(ahead, limit);
+            if queued >= self.cfg.queue_size {
+                return Err(AgentError::RateLimit(
+                    format!("admission queue is full ({} waiting)", queued),
+                    Some(retry_after_secs(estimate_ms)),
+                ));
+            }
+            if estimate_ms > max_wait_ms {
+                return Err(AgentError::NoCapacity(
+                    format!(
+                        "tier {} is saturated: estimated wait {:.0}ms exceeds {:.0}ms",
+                        tier.as_str(),
+                        estimate_ms,
+                        max_wait_ms
+                    ),
+                    Some(retry_after_secs(estimate_ms)),
+                ));
+            }
+
+            let (wake, rx) = oneshot::channel();
+            let seq = state.seq;
+            state.seq += 1;
+            state.queued += 1;
+            state.tiers.entry(tier).or_default().waiters.push(Waiter {
+                priority,
+                seq,
+                wake,
+            });
+            (rx, seq)
+        };
+
+        let mut ticket = Ticket {
+            admission: self,
+            tier,
+            seq,
+            rx,
+            settled: false,
+        };
+        let granted = tokio::time::timeout(self.cfg.max_wait, &mut ticket.rx)
+            .await
+            .is_ok_and(|r| r.is_ok());
+        // A slot may be handed over just as the wait expires.
+        if granted || ticket.abandon() {
+            ticket.settled = true;
+            return Ok(Permit {
+                admission: self,
+                tier,
+                since: Instant::now(),
+                limited: true,
+            });
+        }
+        let estimate_ms = {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let t = state.tiers.get(&tier);
+            let ahead = t.map_or(0, |t| {
+                t.waiters.iter().filter(|w| w.priority >= priority).count()
+            });
+            t.map_or(0.0, |t| t.wait_estimate_ms(ahead + 1, limit))
+        };
+        Err(AgentError::NoCapacity(
+            format!(
+                "timed out after {}ms waiting for tier {} capacity",
+                self.cfg.max_wait.as_millis(),
+                tier.as_str()
+            ),
+            Some(retry_after_secs(estimate_ms)),
+        ))
+    }
+
+    /// Requests waiting for `tier`.
+    pub fn queued(&self, tier: Tier) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.tiers.get(&tier).map(|t| t.waiters.len()).unwrap_or(0)
+    }
+
+    /// Frees a slot, handing it straight to the best waiter if any.
+    /// `held` feeds the wait estimate; slots never used pass `None`.
+    fn release(&self, tier: Tier, held: Option<Duration>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let State { tiers, queued, .. } = &mut *state;
+        let t = tiers.entry(tier).or_default();
+        t.in_flight -= 1;
+        if let Some(held) = held {
+            let held_ms = held.as_secs_f64() * 1000.0;
+            t.hold_ewma_ms = if t.hold_ewma_ms == 0.0 {
+                held_ms
+            } else {
+                EWMA_ALPHA * held_ms + (1.0 - EWMA_ALPHA) * t.hold_ewma_ms
+            };
+        }
+        while let Some(w) = t.waiters.pop() {
+            *queued -= 1;
+            if w.wake.send(()).is_ok() {
+                t.in_flight += 1;
+                break;
+            }
+        }
+    }
+}
+
+/// A queued request. Dropping it unsettled (the caller gave up) leaves
+/// the queue, or gives back a slot handed over meanwhile.
+struct Ticket<'a> {
+    admission: &'a Admission,
+    tier: Tier,
+    seq: u64,
+    rx: oneshot::Receiver<()>,
+    settled: bool,
+}
+
+impl Ticket<'_> {
+    /// Leaves the queue; returns whether a slot was granted meanwhile.
+    fn abandon(&mut self) -> bool {
+        self.settled = true;
+        let mut state = self
+            .admission
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let t = state.tiers.entry(self.tier).or_default();
+        let before = t.waiters.len();
+        t.waiters.retain(|w| w.seq != self.seq);
+        if t.waiters.len() < before {
+            state.queued -= 1;
+            return false;
+        }
+        self.rx.try_recv().is_ok()
+    }
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        if !self.settled && self.abandon() {
+            self.admission.release(self.tier, None);
+        }
+    }
+}
+
+/// A tier slot, released on drop.
+pub struct Permit<'a> {
+    admission: &'a Admission,
+    tier: Tier,
+    since: Instant,
+    limited: bool,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.limited {
+            self.admission
+                .release(self.tier, Some(self.since.elapsed()));
+        }
+    }
+}
@@ -5,13 +5,36 @@
 //     node routing, and LLM request handling.
 //
 use crate::{
+    admission::{Admission, AdmissionConfig},
+    batch::BatchConfig,
+    cache::{self, CachedGeneration, ResponseCache},
+    chat_template::{ChatTemplates, TemplateMessage},
+    coalesce::SingleFlight,
     config::AppConfig,
-    models::{ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Choice, Usage, new_id, Tier},
-    node_client::{NodeClient, NodeGenerateRequest},
-    policy::PolicyEngine,
+    embeddings::{self, EncodingFormat},
+    error::AgentError,
+    keystore::{self, AgentIdentity, Keystore},
+    models::{
+        new_id, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatMessage,
+        Choice, ChunkChoice, ChunkDelta, CompletionChoice, CompletionRequest, CompletionResponse,
+        Embedding, EmbeddingRequest, EmbeddingResponse, EmbeddingUsage, MessageContent, ModelInfo,
+        ModelPricing, SamplingParams, Tier, ToolCall, ToolCallDelta, Usage,
+    },
+    multimodal::{self, ImageInput},
+    node_client::{Delivery, NodeClient, NodeEmbedRequest, NodeGenerateRequest},
+    policy::{AppliedPrompt, PolicyEngine, MAX_CHOICES},
+    receipts::{Ledger, UsageReceipt},
+    reconcile::{NodeTokenStats, ReconcileConfig, Reconciler},
     routing::{NodePool, NodeRouter, RoundRobinRouter},
+    structured::OutputSchema,
+    tokenizer::TokenizerRegistry,
+    tools::{self, ToolMode},
 };
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use time::OffsetDateTime;
+use tokio::task::JoinSet;
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct AuriaAgent {
@@ -19,22 +42,106 @@ pub struct AuriaAgent {
     policy: PolicyEngine,
     pool: NodePool,
     router: std::sync::Arc<dyn NodeRouter>,
+    ledger: Option<Ledger>,
+    identity: Option<AgentIdentity>,
+    tokenizers: TokenizerRegistry,
+    templates: ChatTemplates,
+    reconciler: std::sync::Arc<Reconciler>,
+    cache: Option<std::sync::Arc<dyn ResponseCache>>,
+    admission: std::sync::Arc<Admission>,
+    inflight: std::sync::Arc<SingleFlight<Vec<Generation>>>,
+    /// Work that outlives its request, such as cancelling node calls.
+    background: std::sync::Arc<std::sync::Mutex<JoinSet<()>>>,
 }
 
 impl AuriaAgent {
     pub async fn new(cfg: AppConfig) -> anyhow::Result<Self> {
+        let timeout = std::time::Duration::from_secs(cfg.node_timeout_secs.max(1));
+        let batching = BatchConfig {
+            window: std::time::Duration::from_millis(cfg.batch_window_ms),
+            max_size: cfg.max_batch_size,
+        };
         let mut nodes = Vec::new();
         for u in &cfg.node_urls {
-            nodes.push(NodeClient::new(u)?);
+            let mut node = NodeClient::new(u, timeout)?;
+            if cfg.batch_window_ms > 0 {
+                node = node.with_batching(batching);
+            }
+            match cfg
+                .node_concurrency_limits
+                .get(u)
+                .copied()
+                .unwrap_or(cfg.node_concurrency)
+            {
+                0 => {}
+                limit => node = node.with_concurrency_limit(limit),
+            }
+            nodes.push(node);
         }
         if nodes.is_empty() {
             anyhow::bail!("no node urls configured");
         }
+        if let Some(u) = cfg
+            .node_concurrency_limits
+            .keys()
+            .find(|u| !cfg.node_urls.contains(u))
+        {
+            anyhow::bail!("node_concurrency_limits: {} is not in node_urls", u);
+        }
+
+        let ledger = match &cfg.receipts_dir {
+            Some(dir) => Some(Ledger::open(dir)?),
+            None => None,
+        };
+        let identity = match &cfg.keystore_path {
+            Some(path) => {
+                Some(Keystore::open(path, &keystore::passphrase_from_env()?)?.identity()?)
+            }
+            None => None,
+        };
+
+        let templates = ChatTemplates::load(&cfg.chat_template_files)?;
+        let named = std::iter::once(&cfg.chat_template)
+            .chain(cfg.tier_specs.values().flat_map(|s| &s.chat_template));
+        if let Some(name) = named.into_iter().find(|n| !templates.contains(n)) {
+            anyhow::bail!("unknown chat template: {}", name);
+        }
+
+        let cache = cache::open(
+            &cfg.response_cache,
+            cfg.response_cache_dir.as_deref(),
+            cfg.response_cache_capacity,
+            std::time::Duration::from_secs(cfg.response_cache_ttl_secs),
+        )?;
 
         Ok(Self {
-            policy: PolicyEngine { default_tier: cfg.default_tier, max_cost_microusdc: cfg.max_cost_microusdc },
+            policy: PolicyEngine {
+                default_tier: cfg.default_tier,
+                max_cost_microusdc: cfg.max_cost_microusdc,
+                system_prompts: cfg.system_prompts.clone(),
+            },
             pool: NodePool { nodes },
             router: std::sync::Arc::new(RoundRobinRouter::default()),
+            ledger,
+            identity,
+            tokenizers: TokenizerRegistry::load(
+                &cfg.tokenizer,
+                &cfg.tokenizer_files,
+                cfg.tokenizer_pattern.as_deref(),
+            )?,
+            templates,
+            reconciler: std::sync::Arc::new(Reconciler::new(ReconcileConfig {
+                tolerance: cfg.reconcile_tolerance,
+                min_samples: cfg.reconcile_min_samples,
+            })),
+            cache,
+            inflight: std::sync::Arc::new(SingleFlight::default()),
+            background: Default::default(),
+            admission: std::sync::Arc::new(Admission::new(AdmissionConfig {
+                tier_limits: cfg.tier_concurrency.clone(),
+                queue_size: cfg.admission_queue_size,
+                max_wait: std::time::Duration::from_millis(cfg.admission_max_wait_ms),
+            })),
             cfg,
         })
     }
@@ -42,60 +149,1282 @@ impl AuriaAgent {
     pub async fn check_nodes(&self) -> anyhow::Result<()> {
         for n in &self.pool.nodes {
             // Best-effort health check; in prod, collect metrics.
-            let _ = n.healthz().await;
+            if let Err(e) = n.refresh().await {
+                warn!("node {} unhealthy: {}", n.base(), e);
+            }
+            if let Some(t) = n.chat_template().filter(|t| !self.templates.contains(t)) {
+                warn!(
+                    "node {} advertises unknown chat template {}; using the tier's",
+                    n.base(),
+                    t
+                );
+            }
         }
         Ok(())
     }
 
-    pub fn config(&self) -> &AppConfig { &self.cfg }
+    /// Every tier as `AURIA:<TIER>` followed by configured aliases.
+    pub fn list_models(&self) -> Vec<ModelInfo> {
+        let mut out: Vec<_> = Tier::ALL
+            .iter()
+            .map(|t| self.model_info(t.model_id(), *t, None))
+            .collect();
+        for (alias, tier) in &self.cfg.model_aliases {
+            out.push(self.model_info(alias.clone(), *tier, Some(tier.model_id())));
+        }
+        out
+    }
 
-    pub async fn chat_completions(&self, req: ChatCompletionRequest) -> anyhow::Result<ChatCompletionResponse> {
-        let requested_tier = parse_model_tier(&req.model).or(Some(self.cfg.default_tier));
-        let pd = self.policy.decide(requested_tier, req.max_tokens);
+    pub fn model(&self, id: &str) -> Option<ModelInfo> {
+        if let Some(tier) = self.cfg.model_aliases.get(id) {
+            return Some(self.model_info(id.to_string(), *tier, Some(tier.model_id())));
+        }
+        parse_model_tier(id).map(|t| self.model_info(t.model_id(), t, None))
+    }
 
-        if !pd.allowed {
-            anyhow::bail!(pd.deny_reason.unwrap_or_else(|| "request denied".to_string()));
+    fn model_info(&self, id: String, tier: Tier, alias_of: Option<String>) -> ModelInfo {
+        let spec = self.cfg.tier_spec(tier);
+        ModelInfo {
+            id,
+            object: "model".to_string(),
+            created: 0,
+            owned_by: "auria".to_string(),
+            tier,
+            alias_of,
+            context_window: spec.context_window,
+            multimodal: spec.multimodal,
+            pricing: ModelPricing {
+                microusdc_per_1k_tokens: spec.price_microusdc_per_1k,
+            },
+            healthy_nodes: self
+                .pool
+                .nodes
+                .iter()
+                .filter(|n| {
+                    n.is_healthy()
+                        && n.serves(tier)
+                        && !self.reconciler.is_flagged(n.base().as_str())
+                })
+                .count(),
         }
+    }
 
-        let prompt = messages_to_prompt(&req.messages);
-        let idx = self.router.pick(pd.tier);
-        let node = self.pool.get(idx);
+    /// Waits for background work (node cancellations and their receipts)
+    /// and syncs the ledger, before the process exits.
+    pub async fn shutdown(&self) {
+        let mut tasks =
+            std::mem::take(&mut *self.background.lock().unwrap_or_else(|e| e.into_inner()));
+        while tasks.join_next().await.is_some() {}
+        if let Some(ledger) = &self.ledger {
+            if let Err(e) = ledger.flush() {
+                warn!("failed to flush the receipt ledger: {}", e);
+            }
+        }
+    }
 
-        let node_resp = node.generate(NodeGenerateRequest {
-            tier: pd.tier,
-            prompt,
-            max_tokens: pd.max_tokens,
-        }).await?;
+    fn spawn_background(&self, task: impl std::future::Future<Output = ()> + Send + 'static) {
+        let Ok(rt) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let mut tasks = self.background.lock().unwrap_or_else(|e| e.into_inner());
+        while tasks.try_join_next().is_some() {}
+        tasks.spawn_on(task, &rt);
+    }
 
-        let content = node_resp.tokens.join("");
-        let created = OffsetDateTime::now_utc().unix_timestamp();
+    pub fn config(&self) -> &AppConfig {
+        &self.cfg
+    }
+
+    pub fn ledger(&self) -> Option<&Ledger> {
+        self.ledger.as_ref()
+    }
+
+    pub fn identity(&self) -> Option<&AgentIdentity> {
+        self.identity.as_ref()
+    }
+
+    /// Per-node discrepancies between reported and locally counted tokens.
+    pub fn token_reconciliation(&self) -> Vec<NodeTokenStats> {
+        self.reconciler.report()
+    }
+
+    /// Tenant and queueing priority of a request, from its API key (matched
+    /// against `tenants`) and request class (`request_classes`).
+    pub fn caller(&self, api_key: Option<&str>, class: Option<&str>) -> Caller {
+        let tenant = api_key.and_then(|k| {
+            self.cfg
+                .tenants
+                .iter()
+                .find(|(_, t)| t.api_keys.iter().any(|a| a == k))
+        });
+        let class_priority = class
+            .and_then(|c| self.cfg.request_classes.get(c))
+            .copied()
+            .unwrap_or(0);
+        Caller {
+            tenant: tenant.map(|(name, _)| name.clone()),
+            priority: tenant.map(|(_, t)| t.priority).unwrap_or(0) + class_priority,
+            request_id: None,
+        }
+    }
+
+    pub async fn chat_completions(
+        &self,
+        req: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        self.chat_completions_for(&Caller::default(), req).await
+    }
+
+    pub async fn chat_completions_for(
+        &self,
+        caller: &Caller,
+        req: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        let run = self.run_chat(caller, &req).await?;
+        let choices = run
+            .outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, o)| Choice {
+                index: i as u32,
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: o.content.map(MessageContent::Text),
+                    name: None,
+                    tool_calls: (!o.tool_calls.is_empty()).then_some(o.tool_calls),
+                    tool_call_id: None,
+                },
+                finish_reason: o.finish_reason.to_string(),
+            })
+            .collect();
 
         Ok(ChatCompletionResponse {
-            id: new_id(),
+            id: run.id,
+            created: run.created,
+            model: req.model,
+            choices,
+            usage: run.usage,
+        })
+    }
+
+    /// Streaming variant of [`chat_completions`](Self::chat_completions):
+    /// the `chat.completion.chunk` events to send, in order. Nodes answer
+    /// whole, so content is relayed one node token per chunk; choices with
+    /// tool calls send their remaining content and calls as whole deltas.
+    pub async fn chat_completion_chunks(
+        &self,
+        req: ChatCompletionRequest,
+    ) -> Result<Vec<ChatCompletionChunk>, AgentError> {
+        self.chat_completion_chunks_for(&Caller::default(), req)
+            .await
+    }
+
+    pub async fn chat_completion_chunks_for(
+        &self,
+        caller: &Caller,
+        req: ChatCompletionRequest,
+    ) -> Result<Vec<ChatCompletionChunk>, AgentError> {
+        let run = self.run_chat(caller, &req).await?;
+        let chunk =
+            |index: u32, delta: ChunkDelta, finish_reason: Option<String>| ChatCompletionChunk {
+                id: run.id.clone(),
+                object: "chat.completion.chunk".to_string(),
+                created: run.created,
+                model: req.model.clone(),
+                choices: vec![ChunkChoice {
+                    index,
+                    delta,
+                    finish_reason,
+                }],
+                usage: None,
+            };
+
+        let mut out = Vec::new();
+        for (i, o) in run.outputs.iter().enumerate() {
+            let index = i as u32;
+            out.push(chunk(
+                index,
+                ChunkDelta {
+                    role: Some("assistant".to_string()),
+                    ..Default::default()
+                },
+                None,
+            ));
+            if o.tool_calls.is_empty() {
+                for t in &o.tokens {
+                    out.push(chunk(
+                        index,
+                        ChunkDelta {
+                            content: Some(t.clone()),
+                            ..Default::default()
+                        },
+                        None,
+                    ));
+                }
+            } else {
+                if let Some(c) = &o.content {
+                    out.push(chunk(
+                        index,
+                        ChunkDelta {
+                            content: Some(c.clone()),
+                            ..Default::default()
+                        },
+                        None,
+                    ));
+                }
+                let calls = o
+                    .tool_calls
+                    .iter()
+                    .enumerate()
+                    .map(|(j, c)| ToolCallDelta {
+                        index: j as u32,
+                        id: c.id.clone(),
+                        kind: c.kind.clone(),
+                        function: c.function.clone(),
+                    })
+                    .collect();
+                out.push(chunk(
+                    index,
+                    ChunkDelta {
+                        tool_calls: Some(calls),
+                        ..Default::default()
+                    },
+                    None,
+                ));
+            }
+            out.push(chunk(
+                index,
+                ChunkDelta::default(),
+                Some(o.finish_reason.to_string()),
+            ));
+        }
+
+        if req
+            .stream_options
+            .as_ref()
+            .map(|o| o.include_usage)
+            .unwrap_or(false)
+        {
+            let mut last = chunk(0, ChunkDelta::default(), None);
+            last.choices.clear();
+            last.usage = Some(run.usage);
+            out.push(last);
+        }
+        Ok(out)
+    }
+
+    /// Runs a chat request: tool rendering, generation, receipts and tool
+    /// call parsing, shared by the plain and streaming responses.
+    async fn run_chat(
+        &self,
+        caller: &Caller,
+        req: &ChatCompletionRequest,
+    ) -> Result<ChatRun, AgentError> {
+        let tools = req.tools.as_deref().unwrap_or_default();
+        let mode = ToolMode::resolve(tools, req.tool_choice.as_ref())
+            .map_err(AgentError::InvalidRequest)?;
+        let schema = match &req.response_format {
+            Some(f) => OutputSchema::from_format(f).map_err(AgentError::InvalidRequest)?,
+            None => None,
+        };
+
+        let tier = self.resolve_tier(&req.model);
+        let limits = self.cfg.image_limits();
+        let images = multimodal::collect_images(&req.messages, limits)
+            .map_err(AgentError::InvalidRequest)?;
+        if !images.is_empty() && !self.cfg.tier_spec(tier).multimodal {
+            return Err(AgentError::InvalidRequest(format!(
+                "tier {} is text-only and does not accept images",
+                tier.as_str()
+            )));
+        }
+
+        let mut messages: Vec<TemplateMessage> =
+            req.messages.iter().map(TemplateMessage::from).collect();
+        let policy_prompts =
+            self.policy
+                .apply_system_prompts(caller.tenant.as_deref(), tier, &mut messages);
+        let mut instructions = String::new();
+        if mode != ToolMode::Disabled {
+            instructions.push_str(&tools::render_tools(tools, &mode));
+        }
+        if let Some(schema) = &schema {
+            instructions.push_str(&schema.instructions());
+        }
+        if !instructions.is_empty() {
+            prepend_system(&mut messages, instructions.trim_end());
+        }
+        let template = self.chat_template(tier);
+        let prompt = self
+            .templates
+            .render(&template, &messages, true)
+            .map_err(AgentError::InvalidRequest)?;
+        let id = caller.request_id();
+        let created = OffsetDateTime::now_utc().unix_timestamp();
+
+        let max_tokens = req.max_completion_tokens.or(req.max_tokens);
+        let n = req.n.unwrap_or(1);
+        let params = GenerateParams {
+            request_id: id.clone(),
+            first_index: (n > 1).then_some(0),
+            model: req.model.clone(),
+            max_tokens,
+            sampling: req.sampling.clone(),
+            guided_json: schema.as_ref().and_then(|s| s.schema().cloned()),
+            images,
+            chat: Some(std::sync::Arc::new(messages.clone())),
+            policy_prompts,
+            n,
+            ..Default::default()
+        };
+        let gens = self.generate(caller, tier, prompt, &params).await?;
+
+        // The prompt is counted once, as OpenAI does, and billed on the first
+        // choice's receipt, so usage matches the sum of the receipts.
+        let mut usage = Usage {
+            prompt_tokens: gens.iter().map(|g| g.prompt_tokens).sum(),
+            completion_tokens: 0,
+            total_tokens: 0,
+            cached: gens.iter().all(|g| g.cached).then_some(true),
+        };
+        // Charged so far over every choice and retry; a retry only runs if
+        // its worst case fits in what is left of `max_cost_microusdc`.
+        let mut spent: u64 = gens.iter().map(|g| self.charge(g)).sum();
+        let mut outputs = Vec::with_capacity(gens.len());
+        for (i, mut g) in gens.into_iter().enumerate() {
+            let receipt_id = params.generation_id(i as u32);
+            self.record_receipt(&receipt_id, created, &req.model, &g);
+            usage.completion_tokens += g.completion_tokens;
+
+            let (mut content, tool_calls) = match mode {
+                ToolMode::Disabled => (Some(g.text.clone()), Vec::new()),
+                _ => tools::parse_tool_calls(&g.text, tools),
+            };
+            tools::check_calls(&mode, &tool_calls)
+                .map_err(|e| AgentError::UpstreamError(format!("choice {}: {}", i, e)))?;
+
+            if let (Some(schema), true) = (&schema, tool_calls.is_empty()) {
+                let mut attempt = 1;
+                let json = loop {
+                    let error = match schema.check(&g.text) {
+                        Ok(json) => break json,
+                        Err(e) => e,
+                    };
+                    if attempt >= self.cfg.structured_output_attempts {
+                        return Err(AgentError::UpstreamError(format!(
+                            "node output did not satisfy response_format after {} attempt(s): {}",
+                            attempt, error
+                        )));
+                    }
+                    attempt += 1;
+                    warn!(
+                        "choice {} of {} failed response_format, retrying: {}",
+                        i, id, error
+                    );
+
+                    let mut retry_messages = messages.clone();
+                    retry_messages.push(TemplateMessage::new("assistant", g.text.clone()));
+                    retry_messages.push(TemplateMessage::new(
+                        "user",
+                        format!("That reply was rejected: {}. Reply again with only the corrected JSON.", error),
+                    ));
+                    let retry_prompt = self
+                        .templates
+                        .render(&template, &retry_messages, true)
+                        .map_err(AgentError::InvalidRequest)?;
+                    let retry = GenerateParams {
+                        request_id: format!("{}:retry{}", receipt_id, attempt),
+                        first_index: None,
+                        chat: Some(std::sync::Arc::new(retry_messages)),
+                        spent_microusdc: spent,
+                        n: 1,
+                        ..params.clone()
+                    };
+                    g = match self.generate(caller, tier, retry_prompt, &retry).await {
+                        Ok(mut gens) => gens.remove(0),
+                        Err(AgentError::Permission(budget)) => {
+                            return Err(AgentError::UpstreamError(format!(
+                                "node output did not satisfy response_format after {} attempt(s): {}; no budget for another: {}",
+                                attempt - 1, error, budget
+                            )))
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    spent += self.charge(&g);
+                    self.record_receipt(
+                        &format!("{}:retry{}", receipt_id, attempt),
+                        created,
+                        &req.model,
+                        &g,
+                    );
+                    usage.prompt_tokens += g.prompt_tokens;
+                    usage.completion_tokens += g.completion_tokens;
+                };
+                // Repaired or retried output no longer matches the node tokens.
+                g.tokens = vec![json.clone()];
+                content = Some(json);
+            }
+
+            outputs.push(ChatOutput {
+                finish_reason: match (tool_calls.is_empty(), g.truncated) {
+                    (false, _) => "tool_calls",
+                    (true, true) => "length",
+                    (true, false) => "stop",
+                },
+                tokens: g.tokens,
+                content,
+                tool_calls,
+            });
+        }
+        usage.total_tokens = usage.prompt_tokens + usage.completion_tokens;
+
+        Ok(ChatRun {
+            id,
+            created,
+            outputs,
+            usage,
+        })
+    }
+
+    /// Legacy plain-prompt completions. Prompts are sent to nodes verbatim,
+    /// without chat templating; each prompt of a batch yields `n` choices.
+    pub async fn completions(
+        &self,
+        req: CompletionRequest,
+    ) -> Result<CompletionResponse, AgentError> {
+        self.completions_for(&Caller::default(), req).await
+    }
+
+    pub async fn completions_for(
+        &self,
+        caller: &Caller,
+        req: CompletionRequest,
+    ) -> Result<CompletionResponse, AgentError> {
+        if req.best_of.unwrap_or(1) > 1 {
+            return Err(AgentError::InvalidRequest(
+                "best_of > 1 is not supported: nodes do not return log probabilities".to_string(),
+            ));
+        }
+        let n = req.n.unwrap_or(1);
+        let single = req.prompt.len() == 1 && n == 1;
+        let prompts = req.prompt.clone().into_vec();
+        if prompts.is_empty() {
+            return Err(AgentError::InvalidRequest(
+                "prompt must not be empty".to_string(),
+            ));
+        }
+
+        let tier = self.resolve_tier(&req.model);
+        let id = caller.request_id();
+        let created = OffsetDateTime::now_utc().unix_timestamp();
+        let mut choices = Vec::with_capacity(prompts.len() * n as usize);
+        let mut usage = Usage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            cached: Some(true),
+        };
+
+        let params = GenerateParams {
+            request_id: id.clone(),
+            model: req.model.clone(),
+            max_tokens: req.max_tokens,
+            suffix: req.suffix.clone(),
+            sampling: req.sampling.clone(),
+            n,
+            ..Default::default()
+        };
+        for prompt in prompts {
+            // Echo returns the client's prompt, never the policy text.
+            let mut sent = prompt.clone();
+            let policy_prompts = self
+                .policy
+                .apply_prompt_prefix(caller.tenant.as_deref(), tier, &mut sent)
+                .map_err(AgentError::Permission)?;
+            // Batched prompts and n > 1 get one id per generation.
+            let params = GenerateParams {
+                first_index: (!single).then_some(choices.len() as u32),
+                policy_prompts,
+                ..params.clone()
+            };
+            let gens = self.generate(caller, tier, sent, &params).await?;
+            usage.prompt_tokens += gens.iter().map(|g| g.prompt_tokens).sum::<u32>();
+            if !gens.iter().all(|g| g.cached) {
+                usage.cached = None;
+            }
+            for (i, g) in gens.into_iter().enumerate() {
+                let index = choices.len() as u32;
+                let receipt_id = params.generation_id(i as u32);
+                self.record_receipt(&receipt_id, created, &req.model, &g);
+
+                usage.completion_tokens += g.completion_tokens;
+                let text = if req.echo.unwrap_or(false) {
+                    format!("{}{}", prompt, g.text)
+                } else {
+                    g.text
+                };
+                choices.push(CompletionChoice {
+                    text,
+                    index,
+                    logprobs: None,
+                    finish_reason: if g.truncated { "length" } else { "stop" }.to_string(),
+                });
+            }
+        }
+        usage.total_tokens = usage.prompt_tokens + usage.completion_tokens;
+
+        Ok(CompletionResponse {
+            id,
+            object: "text_completion".to_string(),
             created,
             model: req.model,
-            choices: vec![Choice {
-                index: 0,
-                message: ChatMessage { role: "assistant".to_string(), content },
-                finish_reason: "stop".to_string(),
-            }],
-            usage: Usage {
-                prompt_tokens: 0,
-                completion_tokens: node_resp.tokens_generated,
-                total_tokens: node_resp.tokens_generated,
+            choices,
+            usage,
+        })
+    }
+
+    /// Embeds every input on one node that advertises embeddings for the
+    /// tier. Usage is the locally counted input tokens.
+    pub async fn embeddings(&self, req: EmbeddingRequest) -> Result<EmbeddingResponse, AgentError> {
+        self.embeddings_for(&Caller::default(), req).await
+    }
+
+    /// Like [`embeddings`](Self::embeddings), admitted within the tier's
+    /// concurrency limit at the caller's priority.
+    pub async fn embeddings_for(
+        &self,
+        caller: &Caller,
+        req: EmbeddingRequest,
+    ) -> Result<EmbeddingResponse, AgentError> {
+        let format = match req.encoding_format.as_deref() {
+            None => EncodingFormat::Float,
+            Some(f) => EncodingFormat::parse(f).ok_or_else(|| {
+                AgentError::InvalidRequest(format!("unsupported encoding_format: {}", f))
+            })?,
+        };
+        let input = req.input.into_vec();
+        if input.is_empty() {
+            return Err(AgentError::InvalidRequest(
+                "input must not be empty".to_string(),
+            ));
+        }
+        if req.dimensions == Some(0) {
+            return Err(AgentError::InvalidRequest(
+                "dimensions must be at least 1".to_string(),
+            ));
+        }
+
+        let tier = self.resolve_tier(&req.model);
+        let pd = self.policy.decide(Some(tier), None);
+        if !pd.allowed {
+            return Err(AgentError::Permission(
+                pd.deny_reason
+                    .unwrap_or_else(|| "request denied".to_string()),
+            ));
+        }
+        let _permit = self.admission.admit(pd.tier, caller.priority).await?;
+        let node = self.pick_embedding_node(pd.tier)?;
+        // Nodes that do not advertise their vector length are checked on the
+        // response instead.
+        if let (Some(d), Some(max)) = (req.dimensions, node.embedding_dimensions()) {
+            if d > max {
+                return Err(AgentError::InvalidRequest(format!(
+                    "dimensions must be between 1 and {}",
+                    max
+                )));
+            }
+        }
+
+        let tokenizer = self.tokenizers.for_tier(pd.tier);
+        let prompt_tokens = input.iter().map(|s| tokenizer.count(s)).sum();
+        let node_resp = node
+            .embed(NodeEmbedRequest {
+                tier: pd.tier,
+                input,
+            })
+            .await
+            .map_err(AgentError::from_node)?;
+
+        let mut data = Vec::with_capacity(node_resp.embeddings.len());
+        for (i, v) in node_resp.embeddings.into_iter().enumerate() {
+            let v = match req.dimensions {
+                Some(d) => embeddings::truncate(v, d)
+                    .map_err(|e| AgentError::InvalidRequest(e.to_string()))?,
+                None => v,
+            };
+            data.push(Embedding {
+                object: "embedding".to_string(),
+                index: i as u32,
+                embedding: embeddings::encode(v, format),
+            });
+        }
+
+        let g = Generation {
+            tier: pd.tier,
+            node: node.base().to_string(),
+            text: String::new(),
+            tokens: Vec::new(),
+            prompt_tokens,
+            completion_tokens: 0,
+            truncated: false,
+            cached: false,
+            coalesced: false,
+            cancelled: false,
+            policy_prompts: Vec::new(),
+        };
+        self.record_receipt(
+            &caller.request_id(),
+            OffsetDateTime::now_utc().unix_timestamp(),
+            &req.model,
+            &g,
+        );
+
+        Ok(EmbeddingResponse {
+            object: "list".to_string(),
+            data,
+            model: req.model,
+            usage: EmbeddingUsage {
+                prompt_tokens,
+                total_tokens: prompt_tokens,
             },
         })
     }
+
+    /// Template for chat prompts on `tier`; nodes advertising their own
+    /// template get the messages re-rendered in [`generate`](Self::generate).
+    fn chat_template(&self, tier: Tier) -> String {
+        self.cfg
+            .tier_spec(tier)
+            .chat_template
+            .unwrap_or_else(|| self.cfg.chat_template.clone())
+    }
+
+    /// Template `node` expects chat prompts for `tier` in. A template the
+    /// gateway does not know is ignored in favour of the tier's.
+    fn node_template(&self, node: &NodeClient, tier: Tier) -> String {
+        node.chat_template()
+            .filter(|t| self.templates.contains(t))
+            .unwrap_or_else(|| self.chat_template(tier))
+    }
+
+    fn resolve_tier(&self, model: &str) -> Tier {
+        self.cfg
+            .model_aliases
+            .get(model)
+            .copied()
+            .or_else(|| parse_model_tier(model))
+            .unwrap_or(self.cfg.default_tier)
+    }
+
+    /// Shared policy -> routing -> node path for every front-end: applies
+    /// policy (including the cost of all `n` generations), then dispatches
+    /// `prompt` as-is `n` times in parallel, spreading calls over nodes, and
+    /// reconciles each result. Generations are returned in dispatch order.
+    /// Temperature-0 requests are answered from the response cache when
+    /// possible, at the cache-hit price, and identical requests in flight
+    /// at the same time share one dispatch. Chat prompts are first
+    /// re-rendered with the template of the node picked for the first call, so
+    /// token counts, cost checks and cache keys match what nodes receive.
+    async fn generate(
+        &self,
+        caller: &Caller,
+        tier: Tier,
+        mut prompt: String,
+        p: &GenerateParams,
+    ) -> Result<Vec<Generation>, AgentError> {
+        let n = p.n;
+        if n == 0 || n > MAX_CHOICES {
+            return Err(AgentError::InvalidRequest(format!(
+                "n must be between 1 and {}",
+                MAX_CHOICES
+            )));
+        }
+        let pd = self.policy.decide(Some(tier), p.max_tokens);
+        if !pd.allowed {
+            return Err(AgentError::Permission(
+                pd.deny_reason
+                    .unwrap_or_else(|| "request denied".to_string()),
+            ));
+        }
+        self.policy
+            .validate_sampling(&p.sampling)
+            .map_err(AgentError::InvalidRequest)?;
+
+        // With no eligible node the gateway template keys the cache lookup;
+        // dispatch then fails as it would have anyway.
+        let node = p.chat.as_ref().and_then(|_| {
+            let params = p.sampling.requested();
+            self.pick_node(pd.tier, &params, !p.images.is_empty(), None)
+                .ok()
+        });
+        let template = p.chat.as_ref().map(|_| match node {
+            Some(node) => self.node_template(node, pd.tier),
+            None => self.chat_template(pd.tier),
+        });
+        if let (Some(messages), Some(t)) = (&p.chat, &template) {
+            if *t != self.chat_template(pd.tier) {
+                prompt = self
+                    .templates
+                    .render(t, messages, true)
+                    .map_err(AgentError::InvalidRequest)?;
+            }
+        }
+        let prompt = RenderedPrompt {
+            text: prompt,
+            template,
+            node,
+        };
+
+        let prompt_tokens = self.tokenizers.for_tier(pd.tier).count(&prompt.text);
+        let key = cache::key(&(
+            pd.tier,
+            &prompt.text,
+            &prompt.template,
+            pd.max_tokens,
+            &p.suffix,
+            &p.sampling,
+            &p.guided_json,
+            &p.images,
+            n,
+        ));
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| cache::is_cacheable(&p.sampling));
+        if let Some(hits) = cache.and_then(|c| c.get(&key)) {
+            self.policy
+                .check_cost(
+                    p.spent_microusdc,
+                    self.cfg.cache_hit_price_microusdc_per_1k,
+                    prompt_tokens + pd.max_tokens,
+                    n,
+                )
+                .map_err(AgentError::Permission)?;
+            return Ok(hits
+                .into_iter()
+                .enumerate()
+                .map(|(i, h)| Generation {
+                    tier: pd.tier,
+                    node: h.node,
+                    text: h.tokens.join(""),
+                    tokens: h.tokens,
+                    prompt_tokens: if i == 0 { prompt_tokens } else { 0 },
+                    completion_tokens: h.completion_tokens,
+                    truncated: h.completion_tokens >= pd.max_tokens,
+                    cached: true,
+                    coalesced: false,
+                    cancelled: false,
+                    policy_prompts: p.policy_prompts.clone(),
+                })
+                .collect());
+        }
+
+        let price = self.cfg.tier_spec(pd.tier).price_microusdc_per_1k;
+        self.policy
+            .check_cost(p.spent_microusdc, price, prompt_tokens + pd.max_tokens, n)
+            .map_err(AgentError::Permission)?;
+
+        let dispatch = || self.dispatch(caller, pd.tier, &prompt, pd.max_tokens, prompt_tokens, p);
+        // Sampled duplicates must each get their own sample.
+        let (mut gens, ran) = match self.cfg.coalesce_requests && cache::is_cacheable(&p.sampling) {
+            true => self.inflight.run(&key, dispatch).await?,
+            false => (dispatch().await?, true),
+        };
+        if !ran {
+            for g in &mut gens {
+                g.coalesced = true;
+            }
+        } else if let Some(cache) = cache {
+            let entry: Vec<CachedGeneration> = gens
+                .iter()
+                .map(|g| CachedGeneration {
+                    node: g.node.clone(),
+                    tokens: g.tokens.clone(),
+                    completion_tokens: g.completion_tokens,
+                })
+                .collect();
+            cache.put(&key, &entry);
+        }
+        Ok(gens)
+    }
+
+    /// Waits for admission, sends `n` node calls in parallel (each within
+    /// its node's concurrency limit) and reconciles each result. Calls
+    /// still running when the request is dropped are cancelled.
+    async fn dispatch(
+        &self,
+        caller: &Caller,
+        tier: Tier,
+        prompt: &RenderedPrompt<'_>,
+        max_tokens: u32,
+        prompt_tokens: u32,
+        p: &GenerateParams,
+    ) -> Result<Vec<Generation>, AgentError> {
+        let _permit = self.admission.admit(tier, caller.priority).await?;
+        let max_wait = std::time::Duration::from_millis(self.cfg.admission_max_wait_ms);
+        let params = p.sampling.requested();
+        // Set when a call fails, so the siblings dropped with it are not
+        // billed as if the client had cancelled.
+        let failed = &AtomicBool::new(false);
+        let mut calls = Vec::with_capacity(p.n as usize);
+        for i in 0..p.n {
+            let node = match prompt.node.filter(|_| i == 0) {
+                Some(node) => node,
+                None => self.pick_node(
+                    tier,
+                    &params,
+                    !p.images.is_empty(),
+                    prompt.template.as_deref(),
+                )?,
+            };
+            let request_id = p.generation_id(i);
+            let req = NodeGenerateRequest {
+                tier,
+                prompt: prompt.text.clone(),
+                max_tokens,
+                suffix: p.suffix.clone(),
+                sampling: p.sampling.clone(),
+                guided_json: p
+                    .guided_json
+                    .clone()
+                    .filter(|_| node.supports_guided_decoding()),
+                images: p.images.clone(),
+                request_id: Some(request_id.clone()),
+            };
+            // The prompt is billed once, with the first choice.
+            let prompt_tokens = if i == 0 { prompt_tokens } else { 0 };
+            let call = async move {
+                let _slot = tokio::time::timeout(max_wait, node.acquire_slot())
+                    .await
+                    .map_err(|_| {
+                        AgentError::NoCapacity(
+                            format!(
+                                "timed out waiting for a request slot on node {}",
+                                node.base()
+                            ),
+                            None,
+                        )
+                    })?;
+                let call = NodeCall {
+                    agent: self,
+                    node,
+                    request_id,
+                    model: &p.model,
+                    tier,
+                    prompt_tokens,
+                    policy_prompts: &p.policy_prompts,
+                    delivery: Delivery::default(),
+                    failed,
+                    done: false,
+                };
+                let resp = node.generate(req, &call.delivery).await;
+                call.finish();
+                Ok::<_, AgentError>((node, prompt_tokens, resp.map_err(AgentError::from_node)?))
+            };
+            calls.push(async move {
+                call.await
+                    .inspect_err(|_| failed.store(true, Ordering::Release))
+            });
+        }
+        let results = futures::future::try_join_all(calls).await?;
+
+        let tokenizer = self.tokenizers.for_tier(tier);
+        Ok(results
+            .into_iter()
+            .map(|(node, prompt_tokens, resp)| {
+                let text = resp.tokens.join("");
+                let tokens = resp.tokens;
+                let (node_url, counted) = (node.base().as_str(), tokenizer.count(&text));
+                let recon = match tokenizer.is_exact() {
+                    true => self
+                        .reconciler
+                        .record(node_url, resp.tokens_generated, counted),
+                    false => {
+                        self.reconciler
+                            .record_estimate(node_url, resp.tokens_generated, counted)
+                    }
+                };
+                Generation {
+                    tier,
+                    node: node.base().to_string(),
+                    text,
+                    tokens,
+                    prompt_tokens,
+                    completion_tokens: recon.billed,
+                    truncated: recon.billed >= max_tokens,
+                    cached: false,
+                    coalesced: false,
+                    cancelled: false,
+                    policy_prompts: p.policy_prompts.clone(),
+                }
+            })
+            .collect())
+    }
+
+    /// Round-robin pick over healthy nodes serving `tier`, preferring nodes
+    /// below their concurrency limit; when every healthy node is busy the
+    /// caller waits for a slot on one. Nodes flagged for over-reporting
+    /// tokens come after the others, and unhealthy nodes after healthy
+    /// ones. Only nodes serving `tier` and supporting every requested
+    /// sampling parameter (and images, if `multimodal`) are eligible at
+    /// all, and with a `template` only nodes expecting chat prompts in it.
+    fn pick_node(
+        &self,
+        tier: Tier,
+        params: &[&str],
+        multimodal: bool,
+        template: Option<&str>,
+    ) -> Result<&NodeClient, AgentError> {
+        if multimodal
+            && !self
+                .pool
+                .nodes
+                .iter()
+                .any(|n| n.serves(tier) && n.is_multimodal())
+        {
+            return Err(AgentError::NoCapacity(
+                format!("no node serving {} accepts images", tier.as_str()),
+                None,
+            ));
+        }
+        if let Some(p) = params.iter().find(|p| {
+            !self
+                .pool
+                .nodes
+                .iter()
+                .any(|n| n.serves(tier) && n.supports(p))
+        }) {
+            return Err(AgentError::InvalidRequest(format!(
+                "unsupported parameter: no node serving {} supports `{}`",
+                tier.as_str(),
+                p
+            )));
+        }
+        let supports_all = |n: &NodeClient| {
+            (!multimodal || n.is_multimodal())
+                && params.iter().all(|p| n.supports(p))
+                && template.iter().all(|t| self.node_template(n, tier) == *t)
+        };
+        let flagged = |n: &NodeClient| self.reconciler.is_flagged(n.base().as_str());
+
+        // Flagged nodes are billed at the local count, so they still serve
+        // when no unflagged node can, ahead of nodes that look down. Those
+        // may have recovered since the last check, but a tier never goes to
+        // a node that does not serve it.
+        for (healthy, allow_flagged) in [(true, false), (true, true), (false, false), (false, true)]
+        {
+            let eligible = |n: &NodeClient| {
+                n.serves(tier)
+                    && supports_all(n)
+                    && (!healthy || n.is_healthy())
+                    && (allow_flagged || !flagged(n))
+            };
+            for free_slot in [true, false] {
+                for _ in 0..self.pool.len() {
+                    let node = self.pool.get(self.router.pick(tier));
+                    if eligible(node) && (!free_slot || node.has_capacity()) {
+                        return Ok(node);
+                    }
+                }
+            }
+        }
+        if !self.pool.nodes.iter().any(|n| n.serves(tier)) {
+            return Err(AgentError::NoCapacity(
+                format!("no node serves {}", tier.as_str()),
+                None,
+            ));
+        }
+        Err(AgentError::InvalidRequest(format!(
+            "unsupported parameters: no single node supports {}",
+            params.join(", ")
+        )))
+    }
+
+    /// Unlike generation there is no fallback: only healthy nodes that
+    /// advertise embeddings for `tier` are eligible.
+    fn pick_embedding_node(&self, tier: Tier) -> Result<&NodeClient, AgentError> {
+        for _ in 0..self.pool.len() {
+            let node = self.pool.get(self.router.pick(tier));
+            if node.is_healthy() && node.serves_embeddings(tier) {
+                return Ok(node);
+            }
+        }
+        Err(AgentError::NoCapacity(
+            format!(
+                "no healthy node serves embeddings for tier {}",
+                tier.as_str()
+            ),
+            None,
+        ))
+    }
+
+    /// Stops a node call the client gave up on and records what the node
+    /// produced. Nodes that cannot cancel are billed the prompt only.
+    async fn cancel_node_call(
+        &self,
+        node: &NodeClient,
+        request_id: &str,
+        model: &str,
+        tier: Tier,
+        prompt_tokens: u32,
+        policy_prompts: Vec<AppliedPrompt>,
+    ) {
+        let completion_tokens = if node.supports_cancel() {
+            match node.cancel(request_id).await {
+                Ok(Some(resp)) => resp.tokens_generated,
+                // The node never started it or has already finished.
+                Ok(None) => return,
+                Err(e) => {
+                    warn!(
+                        "failed to cancel {} on node {}: {}",
+                        request_id,
+                        node.base(),
+                        e
+                    );
+                    0
+                }
+            }
+        } else {
+            0
+        };
+        let g = Generation {
+            tier,
+            node: node.base().to_string(),
+            text: String::new(),
+            tokens: Vec::new(),
+            prompt_tokens,
+            completion_tokens,
+            truncated: false,
+            cached: false,
+            coalesced: false,
+            cancelled: true,
+            policy_prompts,
+        };
+        self.record_receipt(
+            request_id,
+            OffsetDateTime::now_utc().unix_timestamp(),
+            model,
+            &g,
+        );
+    }
+
+    /// What the receipt of `g` charges: cache hits at the cache-hit price,
+    /// everything else at the tier price.
+    fn charge(&self, g: &Generation) -> u64 {
+        let price = match g.cached {
+            true => self.cfg.cache_hit_price_microusdc_per_1k,
+            false => self.cfg.tier_spec(g.tier).price_microusdc_per_1k,
+        };
+        PolicyEngine::estimate_cost(price, g.prompt_tokens + g.completion_tokens, 1)
+    }
+
+    fn record_receipt(&self, request_id: &str, created: i64, model: &str, g: &Generation) {
+        let total_tokens = g.prompt_tokens + g.completion_tokens;
+        let mut receipt = UsageReceipt {
+            request_id: request_id.to_string(),
+            created,
+            model: model.to_string(),
+            tier: g.tier,
+            node: g.node.clone(),
+            prompt_tokens: g.prompt_tokens,
+            completion_tokens: g.completion_tokens,
+            total_tokens,
+            cached: g.cached.then_some(true),
+            coalesced: g.coalesced.then_some(true),
+            cancelled: g.cancelled.then_some(true),
+            policy_prompts: g.policy_prompts.clone(),
+            charge_microusdc: Some(self.charge(g)),
+            signer: None,
+            signature: None,
+        };
+        if let Some(id) = &self.identity {
+            receipt.signer = id.ed25519_public_hex();
+            receipt.signature = id.sign_ed25519(&receipt.signing_payload());
+        }
+        // Receipts are best-effort: a ledger failure must not fail the request.
+        if let Some(ledger) = &self.ledger {
+            if let Err(e) = ledger.append(&receipt) {
+                warn!("failed to record receipt {}: {}", receipt.request_id, e);
+            }
+        }
+    }
+}
+
+/// A node call in flight. Dropped before [`finish`](Self::finish), as when
+/// the client disconnects and axum drops the handler, it cancels the call
+/// in the background if the request was sent.
+struct NodeCall<'a> {
+    agent: &'a AuriaAgent,
+    node: &'a NodeClient,
+    request_id: String,
+    model: &'a str,
+    tier: Tier,
+    prompt_tokens: u32,
+    policy_prompts: &'a [AppliedPrompt],
+    delivery: Delivery,
+    /// Whether a sibling call of the same request failed.
+    failed: &'a AtomicBool,
+    done: bool,
+}
+
+impl NodeCall<'_> {
+    fn finish(mut self) {
+        self.done = true;
+    }
+}
+
+impl Drop for NodeCall<'_> {
+    fn drop(&mut self) {
+        // Never sent, e.g. still waiting in a batch window: nothing to stop
+        // or bill.
+        if self.done || self.delivery.abandon() {
+            return;
+        }
+        let (agent, node) = (self.agent.clone(), self.node.clone());
+        let (request_id, model) = (std::mem::take(&mut self.request_id), self.model.to_string());
+        // A sibling call failed: the request failed rather than being
+        // cancelled by the client, so the node is stopped but not billed.
+        if self.failed.load(Ordering::Acquire) {
+            if node.supports_cancel() {
+                self.agent.spawn_background(async move {
+                    if let Err(e) = node.cancel(&request_id).await {
+                        warn!(
+                            "failed to cancel {} on node {}: {}",
+                            request_id,
+                            node.base(),
+                            e
+                        );
+                    }
+                });
+            }
+            return;
+        }
+        let (tier, prompt_tokens, policy_prompts) =
+            (self.tier, self.prompt_tokens, self.policy_prompts.to_vec());
+        self.agent.spawn_background(async move {
+            agent
+                .cancel_node_call(
+                    &node,
+                    &request_id,
+                    &model,
+                    tier,
+                    prompt_tokens,
+                    policy_prompts,
+                )
+                .await
+        });
+    }
+}
+
+/// Who a request is served for.
+#[derive(Clone, Debug, Default)]
+pub struct Caller {
+    /// Tenant matched by API key, if any.
+    pub tenant: Option<String>,
+    /// Admission queue priority; higher is served first.
+    pub priority: i32,
+    /// Id assigned by the HTTP layer, used as the response and receipt id;
+    /// a fresh one is generated when unset.
+    pub request_id: Option<String>,
+}
+
+impl Caller {
+    fn request_id(&self) -> String {
+        self.request_id.clone().unwrap_or_else(new_id)
+    }
+}
+
+/// Per-request settings of [`AuriaAgent::generate`].
+#[derive(Clone, Default)]
+struct GenerateParams {
+    /// Id of the node calls and their receipts; see [`Self::generation_id`].
+    request_id: String,
+    /// Choice index of the first generation when ids carry one.
+    first_index: Option<u32>,
+    model: String,
+    max_tokens: Option<u32>,
+    suffix: Option<String>,
+    sampling: SamplingParams,
+    /// JSON Schema forwarded to nodes that advertise guided decoding.
+    guided_json: Option<serde_json::Value>,
+    images: Vec<ImageInput>,
+    /// Chat messages behind the prompt, for nodes with their own template.
+    chat: Option<std::sync::Arc<Vec<TemplateMessage>>>,
+    /// Policy system prompts applied to `chat`, for the receipts.
+    policy_prompts: Vec<AppliedPrompt>,
+    /// Already charged to the request, e.g. by earlier structured-output
+    /// attempts; counts against `max_cost_microusdc`.
+    spent_microusdc: u64,
+    n: u32,
+}
+
+impl GenerateParams {
+    /// Id of generation `i`, used both for its node call and its receipt:
+    /// `<request_id>:<first_index + i>`, or the bare `request_id` for a
+    /// single generation without an index.
+    fn generation_id(&self, i: u32) -> String {
+        match self.first_index {
+            Some(first) => format!("{}:{}", self.request_id, first + i),
+            None => self.request_id.clone(),
+        }
+    }
+}
+
+/// A prompt as sent to nodes.
+struct RenderedPrompt<'a> {
+    text: String,
+    /// Chat template `text` was rendered with; only nodes expecting it may
+    /// serve the prompt. `None` for raw completion prompts.
+    template: Option<String>,
+    /// Node picked to choose `template`; it serves the first call.
+    node: Option<&'a NodeClient>,
+}
+
+/// Result of one node generation, after reconciliation.
+#[derive(Clone)]
+struct Generation {
+    tier: Tier,
+    node: String,
+    text: String,
+    /// Node output as returned, before joining into `text`.
+    tokens: Vec<String>,
+    /// Prompt tokens billed with this generation; of `n` choices only the
+    /// first carries the prompt.
+    prompt_tokens: u32,
+    completion_tokens: u32,
+    /// Stopped by the `max_tokens` budget rather than by the model.
+    truncated: bool,
+    /// Served from the response cache rather than a node.
+    cached: bool,
+    /// Shared with a concurrent identical request that made the node call.
+    coalesced: bool,
+    /// Stopped early because the client went away.
+    cancelled: bool,
+    policy_prompts: Vec<AppliedPrompt>,
+}
+
+struct ChatRun {
+    id: String,
+    created: i64,
+    outputs: Vec<ChatOutput>,
+    usage: Usage,
+}
+
+/// One chat choice after tool call parsing.
+struct ChatOutput {
+    tokens: Vec<String>,
+    content: Option<String>,
+    tool_calls: Vec<ToolCall>,
+    finish_reason: &'static str,
 }
 
-fn messages_to_prompt(msgs: &[crate::models::ChatMessage]) -> String {
-    // Production: apply prompt templates, system policies, tool calls, etc.
-    let mut out = String::new();
-    for m in msgs {
-        out.push_str(&format!("{}: {}
-", m.role, m.content));
+/// Puts agent instructions (tools, response format) in front of the
+/// conversation, merged into a leading system message if there is one.
+fn prepend_system(messages: &mut Vec<TemplateMessage>, instructions: &str) {
+    match messages.first_mut() {
+        Some(m) if m.role == "system" => m.content = format!("{}\n\n{}", instructions, m.content),
+        _ => messages.insert(0, TemplateMessage::new("system", instructions)),
     }
-    out
 }
 
 fn parse_model_tier(model: &str) -> Option<Tier> {
@@ -0,0 +1,474 @@
+// File: anthropic.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Anthropic Messages API front-end: request/response models and
+//     translation to and from the agent's OpenAI-style chat model,
+//     including streaming events and error bodies.
+//
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    error::AgentError,
+    models::{
+        ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChatMessage,
+        ContentPart, FunctionCall, FunctionDef, FunctionName, ImageUrl, MessageContent,
+        SamplingParams, Stop, StreamOptions, Tool, ToolCall, ToolChoice,
+    },
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct MessagesRequest {
+    pub model: String,
+    pub max_tokens: u32,
+    pub messages: Vec<Message>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPrompt>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Not supported by nodes; rejected when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<AnthropicTool>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<AnthropicToolChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+/// Top-level `system`: a string or text blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Message {
+    pub role: String,
+    pub content: Content,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Content {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        /// Text or blocks; documented as an untyped value to break the
+        /// `Content` / `ContentBlock` cycle.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[schema(value_type = Option<Object>)]
+        content: Option<Content>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        is_error: Option<bool>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnthropicTool {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: Value,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicToolChoice {
+    Auto,
+    Any,
+    Tool { name: String },
+    None,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct MessagesResponse {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub role: String,
+    pub model: String,
+    pub content: Vec<ContentBlock>,
+    pub stop_reason: Option<String>,
+    pub stop_sequence: Option<String>,
+    pub usage: AnthropicUsage,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AnthropicUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+/// A server-sent event: `event: <name>` plus its JSON `data`.
+#[derive(Clone, Debug)]
+pub struct StreamEvent {
+    pub event: &'static str,
+    pub data: Value,
+}
+
+/// Translates a Messages request into the agent's chat request.
+pub fn to_chat_request(req: MessagesRequest) -> Result<ChatCompletionRequest, AgentError> {
+    if req.top_k.is_some() {
+        return Err(AgentError::InvalidRequest(
+            "top_k is not supported".to_string(),
+        ));
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system) = &req.system {
+        let text = match system {
+            SystemPrompt::Text(s) => s.clone(),
+            SystemPrompt::Blocks(b) => text_of(&Content::Blocks(b.clone())),
+        };
+        messages.push(ChatMessage::new("system", text));
+    }
+    for m in req.messages {
+        if m.role != "user" && m.role != "assistant" {
+            return Err(AgentError::InvalidRequest(format!(
+                "unexpected message role: {}",
+                m.role
+            )));
+        }
+        messages.extend(translate_message(&m.role, m.content));
+    }
+
+    let tools = req.tools.map(|ts| {
+        ts.into_iter()
+            .map(|t| Tool {
+                kind: "function".to_string(),
+                function: FunctionDef {
+                    name: t.name,
+                    description: t.description,
+                    parameters: Some(t.input_schema),
+                },
+            })
+            .collect()
+    });
+    let tool_choice = req.tool_choice.map(|c| match c {
+        AnthropicToolChoice::Auto => ToolChoice::Mode("auto".to_string()),
+        AnthropicToolChoice::Any => ToolChoice::Mode("required".to_string()),
+        AnthropicToolChoice::None => ToolChoice::Mode("none".to_string()),
+        AnthropicToolChoice::Tool { name } => ToolChoice::Function {
+            kind: "function".to_string(),
+            function: FunctionName { name },
+        },
+    });
+
+    Ok(ChatCompletionRequest {
+        model: req.model,
+        messages,
+        max_tokens: Some(req.max_tokens),
+        max_completion_tokens: None,
+        n: None,
+        tools,
+        tool_choice,
+        stream: req.stream,
+        stream_options: Some(StreamOptions {
+            include_usage: true,
+        }),
+        response_format: None,
+        sampling: SamplingParams {
+            temperature: req.temperature,
+            top_p: req.top_p,
+            stop: req.stop_sequences.map(Stop::Many),
+            ..Default::default()
+        },
+    })
+}
+
+/// One Anthropic message becomes one chat message, plus a `tool` message
+/// per `tool_result` block.
+fn translate_message(role: &str, content: Content) -> Vec<ChatMessage> {
+    let blocks = match content {
+        Content::Text(s) => return vec![ChatMessage::new(role, s)],
+        Content::Blocks(b) => b,
+    };
+
+    let mut parts = Vec::new();
+    let mut calls = Vec::new();
+    let mut results = Vec::new();
+    for block in blocks {
+        match block {
+            ContentBlock::Text { text } => parts.push(ContentPart::Text { text }),
+            ContentBlock::Image { source } => {
+                let url = match source {
+                    ImageSource::Base64 { media_type, data } => {
+                        format!("data:{};base64,{}", media_type, data)
+                    }
+                    ImageSource::Url { url } => url,
+                };
+                parts.push(ContentPart::ImageUrl {
+                    image_url: ImageUrl { url, detail: None },
+                });
+            }
+            ContentBlock::ToolUse { id, name, input } => calls.push(ToolCall {
+                id,
+                kind: "function".to_string(),
+                function: FunctionCall {
+                    name,
+                    arguments: input.to_string(),
+                },
+            }),
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } => {
+                let text = content.as_ref().map(text_of).unwrap_or_default();
+                let text = if is_error.unwrap_or(false) {
+                    format!("Error: {}", text)
+                } else {
+                    text
+                };
+                results.push(ChatMessage {
+                    tool_call_id: Some(tool_use_id),
+                    ..ChatMessage::new("tool", text)
+                });
+            }
+        }
+    }
+
+    let mut out = results;
+    if !parts.is_empty() || !calls.is_empty() {
+        out.push(ChatMessage {
+            role: role.to_string(),
+            content: (!parts.is_empty()).then_some(MessageContent::Parts(parts)),
+            name: None,
+            tool_calls: (!calls.is_empty()).then_some(calls),
+            tool_call_id: None,
+        });
+    }
+    out
+}
+
+/// Translates the agent's chat response back into a Messages response.
+pub fn from_chat_response(resp: ChatCompletionResponse) -> MessagesResponse {
+    let choice = resp.choices.into_iter().next();
+    let mut content = Vec::new();
+    let mut finish_reason = None;
+    if let Some(c) = choice {
+        if let Some(text) = c.message.content.as_ref().and_then(MessageContent::as_text) {
+            if !text.is_empty() {
+                content.push(ContentBlock::Text {
+                    text: text.to_string(),
+                });
+            }
+        }
+        for call in c.message.tool_calls.into_iter().flatten() {
+            content.push(tool_use(&call));
+        }
+        finish_reason = Some(c.finish_reason);
+    }
+
+    MessagesResponse {
+        id: resp.id,
+        kind: "message".to_string(),
+        role: "assistant".to_string(),
+        model: resp.model,
+        content,
+        stop_reason: finish_reason.as_deref().map(stop_reason),
+        stop_sequence: None,
+        usage: AnthropicUsage {
+            input_tokens: resp.usage.prompt_tokens,
+            output_tokens: resp.usage.completion_tokens,
+        },
+    }
+}
+
+/// Translates streamed chat chunks (requested with `include_usage`) into
+/// Messages streaming events, from `message_start` to `message_stop`.
+pub fn stream_events(chunks: &[ChatCompletionChunk]) -> Vec<StreamEvent> {
+    let Some(first) = chunks.first() else {
+        return Vec::new();
+    };
+    let usage = chunks.iter().find_map(|c| c.usage.clone());
+    let (input_tokens, output_tokens) = usage
+        .map(|u| (u.prompt_tokens, u.completion_tokens))
+        .unwrap_or_default();
+
+    let mut out = vec![StreamEvent {
+        event: "message_start",
+        data: json!({ "type": "message_start", "message": {
+            "id": first.id, "type": "message", "role": "assistant", "model": first.model,
+            "content": [], "stop_reason": null, "stop_sequence": null,
+            "usage": { "input_tokens": input_tokens, "output_tokens": 0 },
+        }}),
+    }];
+
+    let mut index = 0;
+    let mut text_open = false;
+    let mut stop = None;
+    // Anthropic has no `n`; only the first choice is relayed.
+    for choice in chunks
+        .iter()
+        .flat_map(|c| &c.choices)
+        .filter(|c| c.index == 0)
+    {
+        if let Some(text) = &choice.delta.content {
+            if !text_open {
+                out.push(block_start(index, json!({ "type": "text", "text": "" })));
+                text_open = true;
+            }
+            out.push(block_delta(
+                index,
+                json!({ "type": "text_delta", "text": text }),
+            ));
+        }
+        for call in choice.delta.tool_calls.iter().flatten() {
+            if text_open {
+                out.push(block_stop(index));
+                index += 1;
+                text_open = false;
+            }
+            out.push(block_start(index, json!({ "type": "tool_use", "id": call.id, "name": call.function.name, "input": {} })));
+            out.push(block_delta(
+                index,
+                json!({ "type": "input_json_delta", "partial_json": call.function.arguments }),
+            ));
+            out.push(block_stop(index));
+            index += 1;
+        }
+        if let Some(r) = &choice.finish_reason {
+            stop = Some(stop_reason(r));
+        }
+    }
+    if text_open {
+        out.push(block_stop(index));
+    }
+
+    out.push(StreamEvent {
+        event: "message_delta",
+        data: json!({
+            "type": "message_delta",
+            "delta": { "stop_reason": stop, "stop_sequence": null },
+            "usage": { "output_tokens": output_tokens },
+        }),
+    });
+    out.push(StreamEvent {
+        event: "message_stop",
+        data: json!({ "type": "message_stop" }),
+    });
+    out
+}
+
+/// Anthropic error body: `{"type": "error", "error": {"type", "message"}}`.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnthropicErrorResponse {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub error: AnthropicErrorDetail,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnthropicErrorDetail {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub message: String,
+}
+
+pub fn error_body(e: &AgentError) -> AnthropicErrorResponse {
+    let kind = match e {
+        AgentError::InvalidRequest(_) | AgentError::IdempotencyMismatch(_) => {
+            "invalid_request_error"
+        }
+        AgentError::Authentication(_) => "authentication_error",
+        AgentError::Permission(_) => "permission_error",
+        AgentError::RateLimit(..) => "rate_limit_error",
+        AgentError::InsufficientQuota(_) => "billing_error",
+        AgentError::NoCapacity(..) => "overloaded_error",
+        AgentError::UpstreamTimeout(_) => "timeout_error",
+        AgentError::UpstreamError(_) | AgentError::Internal(_) => "api_error",
+    };
+    AnthropicErrorResponse {
+        kind: "error".to_string(),
+        error: AnthropicErrorDetail {
+            kind: kind.to_string(),
+            message: e.to_string(),
+        },
+    }
+}
+
+fn stop_reason(finish_reason: &str) -> String {
+    match finish_reason {
+        "tool_calls" => "tool_use",
+        "length" => "max_tokens",
+        _ => "end_turn",
+    }
+    .to_string()
+}
+
+fn tool_use(call: &ToolCall) -> ContentBlock {
+    ContentBlock::ToolUse {
+        id: call.id.clone(),
+        name: call.function.name.clone(),
+        input: serde_json::from_str(&call.function.arguments).unwrap_or_else(|_| json!({})),
+    }
+}
+
+fn text_of(content: &Content) -> String {
+    match content {
+        Content::Text(s) => s.clone(),
+        Content::Blocks(blocks) => blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+fn block_start(index: u32, block: Value) -> StreamEvent {
+    StreamEvent {
+        event: "content_block_start",
+        data: json!({ "type": "content_block_start", "index": index, "content_block": block }),
+    }
+}
+
+fn block_delta(index: u32, delta: Value) -> StreamEvent {
+    StreamEvent {
+        event: "content_block_delta",
+        data: json!({ "type": "content_block_delta", "index": index, "delta": delta }),
+    }
+}
+
+fn block_stop(index: u32) -> StreamEvent {
+    StreamEvent {
+        event: "content_block_stop",
+        data: json!({ "type": "content_block_stop", "index": index }),
+    }
+}
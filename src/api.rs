@@ -4,49 +4,721 @@
 //     HTTP API server using Axum framework for OpenAI-compatible
 //     chat completion endpoints.
 //
+use std::{
+    convert::Infallible,
+    future::{Future, IntoFuture},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{get, post},
+    extract::{rejection::JsonRejection, DefaultBodyLimit, Extension, Path, Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post, MethodRouter},
     Json, Router,
 };
+use serde::{Deserialize, Serialize};
+use tokio::{net::TcpListener, sync::watch};
 use tower_http::trace::TraceLayer;
+use tracing::{info, warn};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
 
-use crate::{config::AppConfig, AuriaAgent, models::ChatCompletionRequest};
+use crate::{
+    agent::Caller,
+    anthropic::{self, AnthropicErrorResponse, MessagesRequest, MessagesResponse},
+    config::AppConfig,
+    error::{AgentError, ErrorDetail, ErrorResponse},
+    idempotency::{self, Claim, IdempotencyStore},
+    keystore::PublicKeyInfo,
+    models::{
+        new_id, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse,
+        CompletionRequest, CompletionResponse, EmbeddingRequest, EmbeddingResponse, ModelInfo,
+        ModelList,
+    },
+    reconcile::NodeTokenStats,
+    AuriaAgent,
+};
 
 #[derive(Clone)]
 struct ApiState {
     agent: AuriaAgent,
+    idempotency: Arc<IdempotencyStore<ChatReply>>,
+    /// Set once shutdown starts: new requests are refused and readiness fails.
+    draining: Arc<AtomicBool>,
+    in_flight: InFlight,
+}
+
+/// Requests being handled, so shutdown can wait for them or cancel them.
+#[derive(Clone)]
+struct InFlight {
+    count: Arc<watch::Sender<usize>>,
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl InFlight {
+    fn new() -> Self {
+        Self {
+            count: Arc::new(watch::Sender::new(0)),
+            cancelled: Arc::new(watch::Sender::new(false)),
+        }
+    }
+
+    /// Runs a request to completion, or `None` if [`cancel`](Self::cancel)
+    /// drops it first (which cancels its node calls).
+    async fn run(&self, request: impl Future<Output = Response>) -> Option<Response> {
+        self.count.send_modify(|n| *n += 1);
+        let _guard = InFlightGuard(self.count.clone());
+        let mut cancelled = self.cancelled.subscribe();
+        tokio::select! {
+            resp = request => Some(resp),
+            _ = cancelled.wait_for(|c| *c) => None,
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    async fn idle(&self) {
+        let _ = self.count.subscribe().wait_for(|n| *n == 0).await;
+    }
+}
+
+struct InFlightGuard(Arc<watch::Sender<usize>>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n -= 1);
+    }
+}
+
+/// OpenAPI 3.1 description of the routes below, served at `/openapi.json`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "AURIA agent API", description = "OpenAI- and Anthropic-compatible inference over AURIA nodes."),
+    paths(
+        healthz,
+        readyz,
+        openapi_json,
+        chat_completions,
+        completions,
+        embeddings,
+        messages,
+        list_models,
+        get_model,
+        agent_keys,
+        token_reconciliation,
+    ),
+    modifiers(&BearerAuth),
+    security((), ("bearerAuth" = []))
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, doc: &mut utoipa::openapi::OpenApi) {
+        let components = doc.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearerAuth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("Agent API key"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Every route served, in the same form as the OpenAPI paths.
+fn route_table() -> Vec<(Method, &'static str, MethodRouter<ApiState>)> {
+    vec![
+        (Method::GET, "/healthz", get(healthz)),
+        (Method::GET, "/readyz", get(readyz)),
+        (Method::GET, "/openapi.json", get(openapi_json)),
+        (Method::POST, "/v1/chat/completions", post(chat_completions)),
+        (Method::POST, "/v1/completions", post(completions)),
+        (Method::POST, "/v1/embeddings", post(embeddings)),
+        (Method::POST, "/v1/messages", post(messages)),
+        (Method::GET, "/v1/models", get(list_models)),
+        (Method::GET, "/v1/models/:id", get(get_model)),
+        (
+            Method::GET,
+            "/.well-known/auria-agent.json",
+            get(agent_keys),
+        ),
+        (
+            Method::GET,
+            "/v1/nodes/reconciliation",
+            get(token_reconciliation),
+        ),
+    ]
+}
+
+/// Method and path of every route, for checking the OpenAPI document
+/// against the router.
+pub fn routes() -> Vec<(Method, &'static str)> {
+    route_table()
+        .into_iter()
+        .map(|(method, path, _)| (method, path))
+        .collect()
 }
 
+/// Serves on `cfg.bind` until SIGTERM or SIGINT, then drains (see
+/// [`serve_with_shutdown`]).
 pub async fn serve(cfg: AppConfig, agent: AuriaAgent) -> anyhow::Result<()> {
-    let state = ApiState { agent };
+    let listener = TcpListener::bind(&cfg.bind).await?;
+    serve_with_shutdown(listener, cfg, agent, shutdown_signal()).await
+}
 
-    let app = Router::new()
-        .route("/healthz", get(healthz))
-        .route("/v1/chat/completions", post(chat_completions))
+/// Serves on `listener` until `shutdown` resolves. Then new requests get
+/// 503 and `/readyz` fails, in-flight requests (streams included) get up
+/// to `shutdown_grace_secs` to finish before they are cancelled, and the
+/// receipt ledger is flushed.
+pub async fn serve_with_shutdown(
+    listener: TcpListener,
+    cfg: AppConfig,
+    agent: AuriaAgent,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    let checker = agent.clone();
+    let interval = Duration::from_secs(cfg.health_check_interval_secs.max(1));
+    tokio::spawn(async move {
+        loop {
+            let _ = checker.check_nodes().await;
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    let idempotency = Arc::new(IdempotencyStore::with_capacity(
+        Duration::from_secs(cfg.idempotency_ttl_secs),
+        cfg.idempotency_capacity,
+    ));
+    let sweeper = idempotency.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(idempotency::SWEEP_INTERVAL).await;
+            sweeper.sweep();
+        }
+    });
+    let state = ApiState {
+        agent: agent.clone(),
+        idempotency,
+        draining: Arc::new(AtomicBool::new(false)),
+        in_flight: InFlight::new(),
+    };
+    let (draining, in_flight) = (state.draining.clone(), state.in_flight.clone());
+
+    // Routes taking images get a body limit large enough for every allowed
+    // image, so the image limits decide; the rest keep axum's default.
+    let image_body_limit = DefaultBodyLimit::max(cfg.image_limits().max_request_bytes());
+    let app = route_table()
+        .into_iter()
+        .fold(Router::new(), |app, (_, path, handler)| match path {
+            "/v1/chat/completions" | "/v1/messages" => {
+                app.route(path, handler.layer(image_body_limit))
+            }
+            _ => app.route(path, handler),
+        })
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            track_requests,
+        ))
+        .layer(middleware::from_fn(assign_request_id))
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
-    let listener = tokio::net::TcpListener::bind(&cfg.bind).await?;
-    axum::serve(listener, app).await?;
+    // The listener stays open while draining so probes still see /readyz fail.
+    let (close, closed) = tokio::sync::oneshot::channel::<()>();
+    let mut server = tokio::spawn(
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = closed.await;
+            })
+            .into_future(),
+    );
+    tokio::select! {
+        result = &mut server => return Ok(result??),
+        _ = shutdown => {}
+    }
+
+    draining.store(true, Ordering::SeqCst);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(cfg.shutdown_grace_secs);
+    info!(
+        "shutting down: draining in-flight requests for up to {}s",
+        cfg.shutdown_grace_secs
+    );
+    if tokio::time::timeout_at(deadline, in_flight.idle())
+        .await
+        .is_err()
+    {
+        warn!("shutdown grace period elapsed; cancelling the remaining requests");
+        in_flight.cancel();
+        in_flight.idle().await;
+    }
+    // Responses already under way (streams) get what is left of the grace period.
+    let _ = close.send(());
+    if let Ok(result) = tokio::time::timeout_at(deadline, &mut server).await {
+        result??;
+    } else {
+        server.abort();
+    }
+    agent.shutdown().await;
+    info!("shutdown complete");
     Ok(())
 }
 
+/// Resolves on SIGINT or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("cannot listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut s) => {
+                s.recv().await;
+            }
+            Err(e) => {
+                warn!("cannot listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = interrupt => {}
+        _ = terminate => {}
+    }
+}
+
+/// Id assigned to a request on arrival. It is the response `id`, the
+/// receipt id and the `x-request-id` header.
+#[derive(Clone)]
+struct RequestId(String);
+
+/// Gives every request a [`RequestId`] and echoes it as `x-request-id`
+/// on the response, unless the handler named another request (idempotent
+/// replays name the one that produced the stored response).
+async fn assign_request_id(mut req: Request, next: Next) -> Response {
+    let id = new_id();
+    req.extensions_mut().insert(RequestId(id.clone()));
+    let mut resp = next.run(req).await;
+    if let Ok(v) = HeaderValue::from_str(&id) {
+        resp.headers_mut().entry("x-request-id").or_insert(v);
+    }
+    resp
+}
+
+/// Tracks requests in flight and, once shutdown starts, refuses new ones
+/// other than the probes.
+async fn track_requests(State(st): State<ApiState>, req: Request, next: Next) -> Response {
+    let path = req.uri().path();
+    if path == "/healthz" || path == "/readyz" {
+        return next.run(req).await;
+    }
+    let anthropic = path == "/v1/messages";
+    let id = req.extensions().get::<RequestId>().cloned();
+    let id = id.unwrap_or_else(|| RequestId(new_id()));
+    let reject = |message: &str| {
+        let e = AgentError::NoCapacity(message.to_string(), None);
+        if anthropic {
+            anthropic_error(e)
+        } else {
+            respond::<()>(&id, Err(e))
+        }
+    };
+    if st.draining.load(Ordering::SeqCst) {
+        return reject("the agent is shutting down");
+    }
+    match st.in_flight.run(next.run(req)).await {
+        Some(resp) => resp,
+        None => reject("the agent shut down before the request finished"),
+    }
+}
+
+#[utoipa::path(get, path = "/healthz", tag = "agent", security(()),
+    responses((status = 200, description = "Liveness probe", body = String, content_type = "text/plain")))]
 async fn healthz() -> impl IntoResponse {
     (StatusCode::OK, "ok")
 }
 
-async fn chat_completions(
+#[utoipa::path(get, path = "/readyz", tag = "agent", security(()),
+    responses(
+        (status = 200, description = "Accepting requests", body = String, content_type = "text/plain"),
+        (status = 503, description = "Shutting down", body = String, content_type = "text/plain"),
+    ))]
+async fn readyz(State(st): State<ApiState>) -> impl IntoResponse {
+    match st.draining.load(Ordering::SeqCst) {
+        true => (StatusCode::SERVICE_UNAVAILABLE, "draining"),
+        false => (StatusCode::OK, "ready"),
+    }
+}
+
+#[utoipa::path(get, path = "/openapi.json", tag = "agent", security(()),
+    responses((status = 200, description = "This document", body = Object)))]
+async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AgentKeys {
+    pub keys: Vec<PublicKeyInfo>,
+}
+
+/// Public identity of this agent, so nodes and settlement services can
+/// verify receipt signatures (retired keys included).
+#[utoipa::path(get, path = "/.well-known/auria-agent.json", tag = "agent", security(()),
+    responses((status = 200, description = "Receipt signing keys", body = AgentKeys)))]
+async fn agent_keys(State(st): State<ApiState>) -> impl IntoResponse {
+    let keys = st
+        .agent
+        .identity()
+        .map(|id| id.public_keys().to_vec())
+        .unwrap_or_default();
+    Json(AgentKeys { keys })
+}
+
+#[utoipa::path(get, path = "/v1/models", tag = "openai",
+    responses((status = 200, description = "Served tiers", body = ModelList)))]
+async fn list_models(State(st): State<ApiState>) -> impl IntoResponse {
+    Json(ModelList {
+        object: "list".to_string(),
+        data: st.agent.list_models(),
+    })
+}
+
+#[utoipa::path(get, path = "/v1/models/{id}", tag = "openai",
+    params(("id" = String, Path, description = "Tier name, model id or alias")),
+    responses(
+        (status = 200, description = "Model", body = ModelInfo),
+        (status = 404, description = "Unknown model", body = ErrorResponse),
+    ))]
+async fn get_model(
     State(st): State<ApiState>,
-    Json(req): Json<ChatCompletionRequest>,
+    Extension(request_id): Extension<RequestId>,
+    Path(id): Path<String>,
 ) -> impl IntoResponse {
-    match st.agent.chat_completions(req).await {
-        Ok(resp) => (StatusCode::OK, Json(resp)).into_response(),
+    match st.agent.model(&id) {
+        Some(m) => (StatusCode::OK, Json(m)).into_response(),
+        None => {
+            let body = ErrorResponse {
+                error: ErrorDetail {
+                    message: format!("The model '{}' does not exist", id),
+                    kind: "invalid_request_error".to_string(),
+                    param: None,
+                    code: Some("model_not_found".to_string()),
+                    request_id: Some(request_id.0),
+                },
+            };
+            (StatusCode::NOT_FOUND, Json(body)).into_response()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationReport {
+    pub nodes: Vec<NodeTokenStats>,
+}
+
+#[utoipa::path(get, path = "/v1/nodes/reconciliation", tag = "agent",
+    responses((status = 200, description = "Per-node token count agreement", body = ReconciliationReport)))]
+async fn token_reconciliation(State(st): State<ApiState>) -> impl IntoResponse {
+    Json(ReconciliationReport {
+        nodes: st.agent.token_reconciliation(),
+    })
+}
+
+#[utoipa::path(post, path = "/v1/chat/completions", tag = "openai", request_body = ChatCompletionRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header,
+            description = "Replays the stored response for a repeated key (scoped by API key; ignored without one) instead of generating again"),
+        ("x-request-class" = Option<String>, Header, description = "Request class; adds its configured admission priority"),
+    ),
+    responses(
+        (status = 200, description = "Completion, or `chat.completion.chunk` events ending in `[DONE]` when `stream` is set",
+            content((ChatCompletionResponse = "application/json"), (ChatCompletionChunk = "text/event-stream")),
+            headers(
+                ("Idempotent-Replayed" = bool, description = "Set when the response is a stored replay"),
+                ("x-auria-cache" = String, description = "`hit` when served from the response cache"),
+            )),
+        (status = "4XX", description = "Invalid request, authentication, permission, rate limit or quota error; \
+            422 when an `Idempotency-Key` is reused with a different body; \
+            429 when the admission queue is full, with `Retry-After`", body = ErrorResponse),
+        (status = "5XX", description = "Upstream node failure, or 503 with `Retry-After` when no capacity frees up in time", body = ErrorResponse),
+    ))]
+async fn chat_completions(
+    State(st): State<ApiState>,
+    Extension(id): Extension<RequestId>,
+    headers: HeaderMap,
+    body: Result<Json<ChatCompletionRequest>, JsonRejection>,
+) -> Response {
+    let req = match body {
+        Ok(Json(req)) => req,
+        Err(e) => return respond::<()>(&id, Err(e.into())),
+    };
+    let caller = caller(&st.agent, &headers, &id);
+    // Anonymous callers would all share one key space and could replay
+    // each other's responses, so only API keys get idempotency.
+    let (Some(key), Some(api_key)) = (headers.get("idempotency-key"), api_key(&headers)) else {
+        return render_chat(&id, run_chat(&st.agent, &caller, req).await);
+    };
+    let Ok(key) = key.to_str() else {
+        return respond::<()>(
+            &id,
+            Err(AgentError::InvalidRequest(
+                "Idempotency-Key must be visible ASCII".to_string(),
+            )),
+        );
+    };
+
+    // The parsed request re-serializes canonically (struct field order,
+    // sorted maps), so formatting differences do not change the fingerprint.
+    let body = match serde_json::to_vec(&req) {
+        Ok(body) => idempotency::fingerprint(&body),
+        Err(e) => return respond::<()>(&id, Err(AgentError::Internal(e.to_string()))),
+    };
+    let scope = idempotency::fingerprint(api_key.as_bytes());
+    match st.idempotency.claim(&scope, key, &body).await {
+        Ok(Claim::Replay(reply)) => {
+            let original = reply.id().map(|id| RequestId(id.to_string()));
+            let mut resp = render_chat(&id, Ok(reply));
+            if let Some(v) = original.and_then(|o| HeaderValue::from_str(&o.0).ok()) {
+                resp.headers_mut().insert("x-request-id", v);
+            }
+            resp.headers_mut()
+                .insert("idempotent-replayed", HeaderValue::from_static("true"));
+            resp
+        }
+        Ok(Claim::Run(lease)) => {
+            let result = run_chat(&st.agent, &caller, req).await;
+            if let Ok(reply) = &result {
+                lease.complete(reply.clone());
+            }
+            render_chat(&id, result)
+        }
+        Err(e) => respond::<()>(&id, Err(e)),
+    }
+}
+
+/// Chat result as stored for idempotent replay.
+#[derive(Clone)]
+enum ChatReply {
+    Completion(ChatCompletionResponse),
+    Stream(Vec<ChatCompletionChunk>),
+}
+
+impl ChatReply {
+    fn id(&self) -> Option<&str> {
+        match self {
+            Self::Completion(resp) => Some(&resp.id),
+            Self::Stream(chunks) => chunks.first().map(|c| c.id.as_str()),
+        }
+    }
+}
+
+async fn run_chat(
+    agent: &AuriaAgent,
+    caller: &Caller,
+    req: ChatCompletionRequest,
+) -> Result<ChatReply, AgentError> {
+    if req.stream.unwrap_or(false) {
+        Ok(ChatReply::Stream(
+            agent.chat_completion_chunks_for(caller, req).await?,
+        ))
+    } else {
+        Ok(ChatReply::Completion(
+            agent.chat_completions_for(caller, req).await?,
+        ))
+    }
+}
+
+fn render_chat(id: &RequestId, result: Result<ChatReply, AgentError>) -> Response {
+    match result {
+        Ok(ChatReply::Completion(resp)) => {
+            let cached = resp.usage.cached.unwrap_or(false);
+            with_cache_status(respond(id, Ok(resp)), cached)
+        }
+        Ok(ChatReply::Stream(chunks)) => {
+            let cached = chunks
+                .iter()
+                .filter_map(|c| c.usage.as_ref())
+                .any(|u| u.cached.unwrap_or(false));
+            with_cache_status(sse(id, chunks), cached)
+        }
+        Err(e) => respond::<()>(id, Err(e)),
+    }
+}
+
+/// Sets `x-auria-cache: hit` on responses served from the response cache.
+fn with_cache_status(mut resp: Response, cached: bool) -> Response {
+    if cached {
+        resp.headers_mut()
+            .insert("x-auria-cache", HeaderValue::from_static("hit"));
+    }
+    resp
+}
+
+/// API key from `Authorization: Bearer <key>`, or Anthropic's `x-api-key`.
+fn api_key(headers: &HeaderMap) -> Option<&str> {
+    match headers.get(header::AUTHORIZATION) {
+        Some(v) => v.to_str().ok()?.strip_prefix("Bearer ").map(str::trim),
+        None => headers.get("x-api-key")?.to_str().ok(),
+    }
+}
+
+/// Tenant and priority from the API key and `x-request-class` header.
+fn caller(agent: &AuriaAgent, headers: &HeaderMap, id: &RequestId) -> Caller {
+    let class = headers.get("x-request-class").and_then(|v| v.to_str().ok());
+    Caller {
+        request_id: Some(id.0.clone()),
+        ..agent.caller(api_key(headers), class)
+    }
+}
+
+/// Anthropic Messages front-end over the same chat path; errors use the
+/// Anthropic body shape.
+#[utoipa::path(post, path = "/v1/messages", tag = "anthropic", request_body = MessagesRequest,
+    params(("x-request-class" = Option<String>, Header, description = "Request class; adds its configured admission priority")),
+    responses(
+        (status = 200, description = "Message, or Anthropic stream events when `stream` is set",
+            content((MessagesResponse = "application/json"), (Object = "text/event-stream"))),
+        (status = "4XX", description = "Client error", body = AnthropicErrorResponse),
+        (status = "5XX", description = "No capacity or upstream node failure", body = AnthropicErrorResponse),
+    ))]
+async fn messages(
+    State(st): State<ApiState>,
+    Extension(id): Extension<RequestId>,
+    headers: HeaderMap,
+    body: Result<Json<MessagesRequest>, JsonRejection>,
+) -> Response {
+    let caller = caller(&st.agent, &headers, &id);
+    let result = async {
+        let req = anthropic::to_chat_request(body?.0)?;
+        if req.stream.unwrap_or(false) {
+            let chunks = st.agent.chat_completion_chunks_for(&caller, req).await?;
+            let events = anthropic::stream_events(&chunks).into_iter().map(|e| {
+                Ok::<_, Infallible>(Event::default().event(e.event).data(e.data.to_string()))
+            });
+            Ok(Sse::new(futures::stream::iter(events)).into_response())
+        } else {
+            let resp = st.agent.chat_completions_for(&caller, req).await?;
+            Ok((StatusCode::OK, Json(anthropic::from_chat_response(resp))).into_response())
+        }
+    };
+    result.await.unwrap_or_else(anthropic_error)
+}
+
+fn anthropic_error(e: AgentError) -> Response {
+    if e.status().is_server_error() {
+        warn!("messages request failed: {}", e);
+    }
+    let mut resp = (e.status(), Json(anthropic::error_body(&e))).into_response();
+    if let Some(secs) = e.retry_after() {
+        resp.headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
+    resp
+}
+
+/// Sends chunks as SSE `data:` events terminated by `data: [DONE]`.
+fn sse<T: serde::Serialize>(id: &RequestId, chunks: Vec<T>) -> Response {
+    let events = chunks
+        .iter()
+        .map(|c| Event::default().json_data(c))
+        .chain(std::iter::once(Ok(Event::default().data("[DONE]"))))
+        .collect::<Result<Vec<_>, _>>();
+    match events {
+        Ok(events) => Sse::new(futures::stream::iter(
+            events.into_iter().map(Ok::<_, Infallible>),
+        ))
+        .into_response(),
+        Err(e) => respond::<()>(id, Err(AgentError::Internal(e.to_string()))),
+    }
+}
+
+#[utoipa::path(post, path = "/v1/completions", tag = "openai", request_body = CompletionRequest,
+    params(("x-request-class" = Option<String>, Header, description = "Request class; adds its configured admission priority")),
+    responses(
+        (status = 200, description = "Legacy text completion", body = CompletionResponse,
+            headers(("x-auria-cache" = String, description = "`hit` when served from the response cache"))),
+        (status = "4XX", description = "Invalid request, authentication, permission, rate limit or quota error; \
+            429 when the admission queue is full, with `Retry-After`", body = ErrorResponse),
+        (status = "5XX", description = "Upstream node failure, or 503 with `Retry-After` when no capacity frees up in time", body = ErrorResponse),
+    ))]
+async fn completions(
+    State(st): State<ApiState>,
+    Extension(id): Extension<RequestId>,
+    headers: HeaderMap,
+    body: Result<Json<CompletionRequest>, JsonRejection>,
+) -> Response {
+    match body {
+        Ok(Json(req)) => match st
+            .agent
+            .completions_for(&caller(&st.agent, &headers, &id), req)
+            .await
+        {
+            Ok(resp) => {
+                let cached = resp.usage.cached.unwrap_or(false);
+                with_cache_status(respond(&id, Ok(resp)), cached)
+            }
+            Err(e) => respond::<()>(&id, Err(e)),
+        },
+        Err(e) => respond::<()>(&id, Err(e.into())),
+    }
+}
+
+#[utoipa::path(post, path = "/v1/embeddings", tag = "openai", request_body = EmbeddingRequest,
+    params(("x-request-class" = Option<String>, Header, description = "Request class; adds its configured admission priority")),
+    responses(
+        (status = 200, description = "Embeddings", body = EmbeddingResponse),
+        (status = "4XX", description = "Invalid request, authentication, permission, rate limit or quota error; \
+            429 when the admission queue is full, with `Retry-After`", body = ErrorResponse),
+        (status = "5XX", description = "Upstream node failure, or 503 with `Retry-After` when no capacity frees up in time", body = ErrorResponse),
+    ))]
+async fn embeddings(
+    State(st): State<ApiState>,
+    Extension(id): Extension<RequestId>,
+    headers: HeaderMap,
+    body: Result<Json<EmbeddingRequest>, JsonRejection>,
+) -> Response {
+    match body {
+        Ok(Json(req)) => respond(
+            &id,
+            st.agent
+                .embeddings_for(&caller(&st.agent, &headers, &id), req)
+                .await,
+        ),
+        Err(e) => respond::<()>(&id, Err(e.into())),
+    }
+}
+
+/// Renders an agent result. Error bodies repeat the request id, which
+/// every response carries as `x-request-id`, as `error.request_id`.
+fn respond<T: serde::Serialize>(id: &RequestId, result: Result<T, AgentError>) -> Response {
+    match result {
+        Ok(body) => (StatusCode::OK, Json(body)).into_response(),
         Err(e) => {
-            let body = serde_json::json!({ "error": { "message": e.to_string(), "type": "auria_error" }});
-            (StatusCode::BAD_REQUEST, Json(body)).into_response()
+            if e.status().is_server_error() {
+                warn!("request {} failed: {}", id.0, e);
+            }
+            e.with_request_id(id.0.clone()).into_response()
         }
     }
 }
@@ -0,0 +1,211 @@
+// File: batch.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Per-node micro-batching of generate requests: compatible requests
+//     arriving within a short window are sent as one batch call and the
+//     results are handed back to each waiting caller.
+//
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+use url::Url;
+
+use crate::{
+    models::Tier,
+    node_client::{Delivery, NodeGenerateRequest, NodeGenerateResponse},
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct BatchConfig {
+    /// How long the first request of a batch waits for company.
+    pub window: Duration,
+    pub max_size: usize,
+}
+
+/// Requests are only batched with others of the same tier and protocol
+/// (text-only or with images).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BatchKey {
+    pub tier: Tier,
+    pub multimodal: bool,
+}
+
+impl BatchKey {
+    pub fn of(req: &NodeGenerateRequest) -> Self {
+        Self {
+            tier: req.tier,
+            multimodal: !req.images.is_empty(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeBatchRequest {
+    pub requests: Vec<NodeGenerateRequest>,
+}
+
+/// One result per request, in request order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeBatchResponse {
+    pub results: Vec<BatchItemResult>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BatchItemResult {
+    Ok(NodeGenerateResponse),
+    Err { error: String },
+}
+
+/// Failure of one batched request: the whole batch call failed, or the
+/// node rejected this item.
+#[derive(Clone, Debug, Error)]
+pub enum BatchError {
+    #[error("batch call timed out: {0}")]
+    Timeout(String),
+    #[error("{0}")]
+    Upstream(String),
+}
+
+struct Item {
+    req: NodeGenerateRequest,
+    delivery: Delivery,
+    reply: oneshot::Sender<Result<NodeGenerateResponse, BatchError>>,
+}
+
+/// Handle to a node's batching task. The task ends when the last handle
+/// is dropped, after flushing what it holds.
+#[derive(Clone, Debug)]
+pub struct Batcher {
+    tx: mpsc::UnboundedSender<Item>,
+}
+
+impl Batcher {
+    /// Spawns the batching task for the node batch endpoint at `url`.
+    pub fn spawn(http: reqwest::Client, url: Url, cfg: BatchConfig) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(collect(http, url, cfg, rx));
+        Self { tx }
+    }
+
+    /// Queues `req` for the next batch; `delivery` is marked sent when the
+    /// batch goes out with it.
+    pub async fn submit(
+        &self,
+        req: NodeGenerateRequest,
+        delivery: Delivery,
+    ) -> Result<NodeGenerateResponse, BatchError> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(Item {
+                req,
+                delivery,
+                reply,
+            })
+            .map_err(|_| BatchError::Upstream("node batcher stopped".to_string()))?;
+        rx.await
+            .map_err(|_| BatchError::Upstream("node batcher dropped the request".to_string()))?
+    }
+}
+
+async fn collect(
+    http: reqwest::Client,
+    url: Url,
+    cfg: BatchConfig,
+    mut rx: mpsc::UnboundedReceiver<Item>,
+) {
+    let mut pending: HashMap<BatchKey, (Instant, Vec<Item>)> = HashMap::new();
+    loop {
+        let deadline = pending.values().map(|(d, _)| *d).min();
+        tokio::select! {
+            item = rx.recv() => match item {
+                Some(item) => {
+                    let key = BatchKey::of(&item.req);
+                    let (_, items) = pending.entry(key).or_insert_with(|| (Instant::now() + cfg.window, Vec::new()));
+                    items.push(item);
+                    if items.len() >= cfg.max_size.max(1) {
+                        let (_, items) = pending.remove(&key).expect("batch present");
+                        tokio::spawn(send(http.clone(), url.clone(), items));
+                    }
+                }
+                None => {
+                    for (_, (_, items)) in pending.drain() {
+                        tokio::spawn(send(http.clone(), url.clone(), items));
+                    }
+                    return;
+                }
+            },
+            _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                let now = Instant::now();
+                let due: Vec<BatchKey> = pending.iter().filter(|(_, (d, _))| *d <= now).map(|(k, _)| *k).collect();
+                for key in due {
+                    let (_, items) = pending.remove(&key).expect("batch present");
+                    tokio::spawn(send(http.clone(), url.clone(), items));
+                }
+            }
+        }
+    }
+}
+
+/// Sends one batch and answers every item still waiting, with the batch
+/// error for all items if the call itself fails.
+async fn send(http: reqwest::Client, url: Url, items: Vec<Item>) {
+    // Callers that went away while the batch filled are not sent.
+    let items: Vec<Item> = items
+        .into_iter()
+        .filter(|i| !i.reply.is_closed() && i.delivery.send())
+        .collect();
+    if items.is_empty() {
+        return;
+    }
+    let (reqs, replies): (Vec<_>, Vec<_>) = items.into_iter().map(|i| (i.req, i.reply)).unzip();
+    let count = reqs.len();
+    match call(&http, url, NodeBatchRequest { requests: reqs }).await {
+        Ok(resp) if resp.results.len() == count => {
+            for (reply, result) in replies.into_iter().zip(resp.results) {
+                let _ = reply.send(match result {
+                    BatchItemResult::Ok(r) => Ok(r),
+                    BatchItemResult::Err { error } => Err(BatchError::Upstream(error)),
+                });
+            }
+        }
+        Ok(resp) => {
+            let e = BatchError::Upstream(format!(
+                "node returned {} results for {} requests",
+                resp.results.len(),
+                count
+            ));
+            for reply in replies {
+                let _ = reply.send(Err(e.clone()));
+            }
+        }
+        Err(e) => {
+            let e = match e.is_timeout() {
+                true => BatchError::Timeout(e.to_string()),
+                false => BatchError::Upstream(e.to_string()),
+            };
+            for reply in replies {
+                let _ = reply.send(Err(e.clone()));
+            }
+        }
+    }
+}
+
+async fn call(
+    http: &reqwest::Client,
+    url: Url,
+    batch: NodeBatchRequest,
+) -> reqwest::Result<NodeBatchResponse> {
+    http.post(url)
+        .json(&batch)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
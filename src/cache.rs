@@ -0,0 +1,232 @@
+// File: cache.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Exact-match response cache for deterministic generations, with
+//     in-memory LRU and on-disk backends.
+//
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use tracing::warn;
+
+use crate::{models::SamplingParams, receipts::now_unix};
+
+/// Result of one node generation as stored in the cache.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedGeneration {
+    /// Node that originally produced the output.
+    pub node: String,
+    pub tokens: Vec<String>,
+    pub completion_tokens: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    expires: i64,
+    generations: Vec<CachedGeneration>,
+}
+
+/// Storage for cached generations. Lookups never fail the request: a
+/// backend error is a miss.
+pub trait ResponseCache: Send + Sync {
+    fn get(&self, key: &str) -> Option<Vec<CachedGeneration>>;
+    fn put(&self, key: &str, generations: &[CachedGeneration]);
+}
+
+/// Only greedy decoding is deterministic enough to replay.
+pub fn is_cacheable(sampling: &SamplingParams) -> bool {
+    sampling.temperature == Some(0.0)
+}
+
+/// Canonical key: hex SHA3-256 of the JSON encoding of `parts`. Struct
+/// fields serialize in declaration order and maps sorted, so equal
+/// requests hash equally.
+pub fn key(parts: &impl Serialize) -> String {
+    let bytes = serde_json::to_vec(parts).expect("cache key serializes");
+    hex::encode(Sha3_256::digest(bytes))
+}
+
+/// Builds the backend named by `response_cache`: "off", "memory" or "disk".
+pub fn open(
+    backend: &str,
+    dir: Option<&str>,
+    capacity: usize,
+    ttl: Duration,
+) -> anyhow::Result<Option<std::sync::Arc<dyn ResponseCache>>> {
+    Ok(match backend {
+        "off" => None,
+        "memory" => Some(std::sync::Arc::new(MemoryCache::new(capacity, ttl))),
+        "disk" => {
+            let dir = dir.ok_or_else(|| {
+                anyhow::anyhow!("response_cache = \"disk\" requires response_cache_dir")
+            })?;
+            Some(std::sync::Arc::new(DiskCache::open(dir, capacity, ttl)?))
+        }
+        other => anyhow::bail!("unknown response_cache backend: {}", other),
+    })
+}
+
+/// In-process LRU bounded by entry count.
+pub struct MemoryCache {
+    ttl: Duration,
+    entries: Mutex<LruCache<String, CacheEntry>>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            ttl,
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<Vec<CachedGeneration>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some(e) if e.expires > now_unix() => Some(e.generations.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put(&self, key: &str, generations: &[CachedGeneration]) {
+        let entry = CacheEntry {
+            expires: expiry(self.ttl),
+            generations: generations.to_vec(),
+        };
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key.to_string(), entry);
+    }
+}
+
+/// One JSON file per key, shared across restarts and processes, bounded
+/// by entry count. Expired entries are removed when read and by
+/// [`sweep`](Self::sweep), which runs on open and whenever writes take the
+/// cache past its capacity.
+pub struct DiskCache {
+    dir: PathBuf,
+    capacity: usize,
+    ttl: Duration,
+    /// Entries found by the last sweep plus those written since.
+    entries: AtomicUsize,
+}
+
+impl DiskCache {
+    pub fn open(dir: impl AsRef<Path>, capacity: usize, ttl: Duration) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        let cache = Self {
+            dir: dir.as_ref().to_path_buf(),
+            capacity: capacity.max(1),
+            ttl,
+            entries: AtomicUsize::new(0),
+        };
+        cache.sweep()?;
+        Ok(cache)
+    }
+
+    /// Removes expired or unreadable entries and temporary files left by
+    /// interrupted writes, then evicts the least recently written entries
+    /// until the cache is at 90% of its capacity, so a full cache is not
+    /// swept on every write.
+    pub fn sweep(&self) -> std::io::Result<()> {
+        let (now, unix_now) = (SystemTime::now(), now_unix());
+        let mut live = Vec::new();
+        for dirent in std::fs::read_dir(&self.dir)? {
+            let dirent = dirent?;
+            let path = dirent.path();
+            let modified = dirent.metadata()?.modified()?;
+            let stale = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") => !matches!(read_entry(&path), Some(e) if e.expires > unix_now),
+                // Renamed into place right after writing; old ones are leftovers.
+                Some("tmp") => now.duration_since(modified).unwrap_or_default() >= TMP_GRACE,
+                _ => continue,
+            };
+            if stale {
+                let _ = std::fs::remove_file(&path);
+            } else if path.extension().is_some_and(|e| e == "json") {
+                live.push((modified, path));
+            }
+        }
+        if live.len() > self.capacity {
+            live.sort();
+            let keep = (self.capacity * 9 / 10).max(1);
+            for (_, path) in live.drain(..live.len() - keep) {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+        self.entries.store(live.len(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// Age after which a temporary file is taken to be left by a failed write.
+const TMP_GRACE: Duration = Duration::from_secs(60);
+
+impl ResponseCache for DiskCache {
+    fn get(&self, key: &str) -> Option<Vec<CachedGeneration>> {
+        let path = self.path(key);
+        let entry = read_entry(&path)?;
+        if entry.expires <= now_unix() {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        Some(entry.generations)
+    }
+
+    fn put(&self, key: &str, generations: &[CachedGeneration]) {
+        let entry = CacheEntry {
+            expires: expiry(self.ttl),
+            generations: generations.to_vec(),
+        };
+        // Write then rename so concurrent readers never see a partial entry.
+        let tmp = self
+            .dir
+            .join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4().simple()));
+        let path = self.path(key);
+        let new = !path.exists();
+        let result = serde_json::to_vec(&entry)
+            .map_err(std::io::Error::from)
+            .and_then(|bytes| std::fs::write(&tmp, bytes))
+            .and_then(|_| std::fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            warn!("failed to write cache entry {}: {}", key, e);
+            let _ = std::fs::remove_file(&tmp);
+            return;
+        }
+        if new && self.entries.fetch_add(1, Ordering::Relaxed) >= self.capacity {
+            if let Err(e) = self.sweep() {
+                warn!("failed to sweep cache {}: {}", self.dir.display(), e);
+            }
+        }
+    }
+}
+
+fn read_entry(path: &Path) -> Option<CacheEntry> {
+    serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+}
+
+fn expiry(ttl: Duration) -> i64 {
+    now_unix().saturating_add(ttl.as_secs() as i64)
+}
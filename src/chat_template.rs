@@ -0,0 +1,167 @@
+// File: chat_template.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Chat templates turning chat messages into the prompt format a
+//     node's model was trained on: built-in plain, ChatML, Llama-3 and
+//     Mistral templates plus Jinja templates loaded from files.
+//
+use std::{collections::BTreeMap, sync::Arc};
+
+use minijinja::{value::ViaDeserialize, Environment, Error, ErrorKind};
+use serde::Serialize;
+
+use crate::{
+    models::{ChatMessage, ContentPart, MessageContent, ToolCall},
+    multimodal, tools,
+};
+
+/// `role: content` lines, as sent before chat templates existed. No
+/// generation prompt: nodes serving it continue the transcript.
+const PLAIN: &str = r#"{% for m in messages %}
+{{ m.role }}{% if m.tool_call_id %} ({{ m.tool_call_id }}){% endif %}: {{ m.content }}{{ m.tool_calls|render_tool_calls }}
+{% endfor %}
+"#;
+
+const CHATML: &str = r#"{% for m in messages %}<|im_start|>{{ m.role }}
+{{ m.content }}{{ m.tool_calls|render_tool_calls }}<|im_end|>
+{% endfor %}{% if add_generation_prompt %}<|im_start|>assistant
+{% endif %}
+"#;
+
+/// Tool results use Llama-3's `ipython` role.
+const LLAMA3: &str = r#"<|begin_of_text|>{% for m in messages %}<|start_header_id|>{{ "ipython" if m.role == "tool" else m.role }}<|end_header_id|>
+
+{{ m.content }}{{ m.tool_calls|render_tool_calls }}<|eot_id|>{% endfor %}
+{% if add_generation_prompt %}<|start_header_id|>assistant<|end_header_id|>
+
+{% endif %}
+"#;
+
+/// Mistral has no system role: system messages are joined and put in
+/// front of the first user message.
+const MISTRAL: &str = r#"{% set ns = namespace(system=messages|selectattr("role", "eq", "system")|map(attribute="content")|join("\n\n")) %}
+<s>{% for m in messages if m.role != "system" %}
+{% if m.role == "user" %}[INST] {% if ns.system %}{{ ns.system }}
+
+{% set ns.system = "" %}{% endif %}{{ m.content }}[/INST]{% elif m.role == "tool" %}[TOOL_RESULTS] {{ m.content }}[/TOOL_RESULTS]{% else %}{{ m.content }}{{ m.tool_calls|render_tool_calls }}</s>{% endif %}
+{% endfor %}
+"#;
+
+/// Built-in templates by name.
+pub const BUILTIN: [(&str, &str); 4] = [
+    ("plain", PLAIN),
+    ("chatml", CHATML),
+    ("llama3", LLAMA3),
+    ("mistral", MISTRAL),
+];
+
+/// One message as seen by templates: `content` is plain text with an
+/// image placeholder per image part, `tool_calls` the assistant's calls.
+#[derive(Clone, Debug, Serialize)]
+pub struct TemplateMessage {
+    pub role: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub tool_calls: Vec<ToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl TemplateMessage {
+    pub fn new(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.into(),
+            name: None,
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+        }
+    }
+}
+
+impl From<&ChatMessage> for TemplateMessage {
+    fn from(m: &ChatMessage) -> Self {
+        let mut content = String::new();
+        for part in m.content.iter().flat_map(MessageContent::parts) {
+            match part {
+                ContentPart::Text { text } => content.push_str(&text),
+                ContentPart::ImageUrl { .. } => content.push_str(multimodal::IMAGE_PLACEHOLDER),
+            }
+        }
+        Self {
+            role: m.role.clone(),
+            content,
+            name: m.name.clone(),
+            tool_calls: m.tool_calls.clone().unwrap_or_default(),
+            tool_call_id: m.tool_call_id.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Context<'a> {
+    messages: &'a [TemplateMessage],
+    add_generation_prompt: bool,
+}
+
+/// Compiled templates, built-in and from files. Rendering follows Hugging
+/// Face chat templates: blocks trim the newline after them and strip the
+/// whitespace before them, and `raise_exception(msg)` fails the request.
+#[derive(Clone)]
+pub struct ChatTemplates {
+    env: Arc<Environment<'static>>,
+}
+
+impl ChatTemplates {
+    /// Loads the built-ins plus `files` (name -> path); a file may replace
+    /// a built-in of the same name.
+    pub fn load(files: &BTreeMap<String, String>) -> anyhow::Result<Self> {
+        let mut env = Environment::new();
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        env.add_filter(
+            "render_tool_calls",
+            |calls: ViaDeserialize<Vec<ToolCall>>| {
+                calls.iter().map(tools::render_call).collect::<String>()
+            },
+        );
+        env.add_function("raise_exception", |msg: String| -> Result<String, Error> {
+            Err(Error::new(ErrorKind::InvalidOperation, msg))
+        });
+        for (name, source) in BUILTIN {
+            env.add_template(name, source)?;
+        }
+        for (name, path) in files {
+            let source = std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("chat template {}: {}: {}", name, path, e))?;
+            env.add_template_owned(name.clone(), source)
+                .map_err(|e| anyhow::anyhow!("chat template {}: {}", name, e))?;
+        }
+        Ok(Self { env: Arc::new(env) })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.env.get_template(name).is_ok()
+    }
+
+    /// Renders `messages` with template `name`, ending in the assistant
+    /// turn header when `add_generation_prompt` is set.
+    pub fn render(
+        &self,
+        name: &str,
+        messages: &[TemplateMessage],
+        add_generation_prompt: bool,
+    ) -> Result<String, String> {
+        let template = self
+            .env
+            .get_template(name)
+            .map_err(|_| format!("unknown chat template: {}", name))?;
+        template
+            .render(Context {
+                messages,
+                add_generation_prompt,
+            })
+            .map_err(|e| format!("chat template {}: {}", name, e))
+    }
+}
@@ -0,0 +1,81 @@
+// File: coalesce.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Single-flight coalescing: concurrent calls with the same key share
+//     the result of one execution.
+//
+use std::{collections::HashMap, future::Future, sync::Mutex};
+
+use tokio::sync::watch;
+
+use crate::error::AgentError;
+
+type Outcome<T> = Option<Result<T, AgentError>>;
+
+/// In-flight executions by key.
+pub struct SingleFlight<T> {
+    flights: Mutex<HashMap<String, watch::Receiver<Outcome<T>>>>,
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            flights: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// Runs `f` unless a call with the same `key` is already in flight, in
+    /// which case its result (or error) is shared. Returns whether this
+    /// caller ran `f`. If the running caller is cancelled, a waiting
+    /// caller runs `f` itself.
+    pub async fn run<F, Fut>(&self, key: &str, f: F) -> Result<(T, bool), AgentError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AgentError>>,
+    {
+        let tx = loop {
+            let mut rx = {
+                let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+                match flights.get(key) {
+                    Some(rx) => rx.clone(),
+                    None => {
+                        let (tx, rx) = watch::channel(None);
+                        flights.insert(key.to_string(), rx);
+                        break tx;
+                    }
+                }
+            };
+            if let Ok(outcome) = rx.wait_for(Option::is_some).await.map(|r| r.clone()) {
+                return outcome.expect("waited for an outcome").map(|v| (v, false));
+            }
+        };
+
+        let flight = Flight {
+            group: self,
+            key,
+            tx,
+        };
+        let result = f().await;
+        flight.tx.send_replace(Some(result.clone()));
+        result.map(|v| (v, true))
+    }
+}
+
+/// Removes the flight when the running caller finishes or is dropped.
+struct Flight<'a, T> {
+    group: &'a SingleFlight<T>,
+    key: &'a str,
+    tx: watch::Sender<Outcome<T>>,
+}
+
+impl<T> Drop for Flight<'_, T> {
+    fn drop(&mut self) {
+        self.group
+            .flights
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(self.key);
+    }
+}
@@ -10,8 +10,60 @@ use figment::{
     Figment,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use crate::models::Tier;
+use crate::{models::Tier, multimodal::ImageLimits};
+
+/// A client organisation, recognised by its API keys.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TenantSpec {
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    /// Admission queue priority; higher is served first.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// How a policy system prompt combines with the client's system prompt.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptMode {
+    #[default]
+    Prepend,
+    Append,
+    /// Drops the client's system messages.
+    Replace,
+}
+
+/// A system prompt the policy engine adds to chat requests.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SystemPromptSpec {
+    /// Recorded on receipts, with a hash of `text`.
+    pub name: String,
+    pub text: String,
+    #[serde(default)]
+    pub mode: PromptMode,
+    /// Tenants it applies to; empty for every caller.
+    #[serde(default)]
+    pub tenants: Vec<String>,
+    /// Tiers it applies to; empty for every tier.
+    #[serde(default)]
+    pub tiers: Vec<Tier>,
+}
+
+/// Static properties of a tier advertised by `/v1/models`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TierSpec {
+    pub context_window: u32,
+    /// Price per 1000 tokens in micro-USDC.
+    pub price_microusdc_per_1k: u64,
+    /// Whether the tier accepts image inputs; text-only tiers refuse them.
+    #[serde(default)]
+    pub multimodal: bool,
+    /// Chat template of the tier's models; `chat_template` if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<String>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -27,6 +79,133 @@ pub struct AppConfig {
     /// Optional max cost guard in micro-USDC.
     /// 0 means "unlimited" in this skeleton.
     pub max_cost_microusdc: u64,
+
+    /// Directory holding the receipt ledger and settlement outbox.
+    /// Receipts are not recorded when unset.
+    pub receipts_dir: Option<String>,
+
+    /// Path to the encrypted agent keystore. When set, receipts are signed
+    /// with the active ed25519 key (passphrase from AURIA_KEYSTORE_PASSPHRASE).
+    pub keystore_path: Option<String>,
+
+    /// EIP-712 domain of the settlement contract (Base mainnet by default).
+    pub settlement_chain_id: u64,
+    pub settlement_contract: Option<String>,
+
+    /// Built-in tokenizer used to count tokens locally for tiers without a
+    /// tokenizer file: "approx" or "whitespace".
+    pub tokenizer: String,
+
+    /// Per-tier tiktoken rank files, e.g. { STANDARD = "/etc/auria/cl100k_base.tiktoken" }.
+    pub tokenizer_files: BTreeMap<Tier, String>,
+
+    /// Pre-tokenization regex for tokenizer files (cl100k_base pattern if unset).
+    pub tokenizer_pattern: Option<String>,
+
+    /// Chat template for tiers and nodes that do not name one: "plain",
+    /// "chatml", "llama3", "mistral" or a name from `chat_template_files`.
+    pub chat_template: String,
+
+    /// Jinja chat templates by name, e.g. { qwen = "/etc/auria/qwen.jinja" }.
+    pub chat_template_files: BTreeMap<String, String>,
+
+    /// Relative over-report tolerated before node token counts are disputed.
+    pub reconcile_tolerance: f64,
+
+    /// Responses observed before a node can be flagged for over-reporting.
+    pub reconcile_min_samples: u64,
+
+    /// Context window and price per tier.
+    pub tier_specs: BTreeMap<Tier, TierSpec>,
+
+    /// Extra model names accepted in requests, e.g. { "gpt-4o-mini" = "STANDARD" }.
+    pub model_aliases: BTreeMap<String, Tier>,
+
+    /// Interval between background node health and capability checks.
+    pub health_check_interval_secs: u64,
+
+    /// Per-request timeout for node calls; expiry is reported as `upstream_timeout`.
+    pub node_timeout_secs: u64,
+
+    /// Largest decoded size of an inline (data URL) image.
+    pub max_image_bytes: usize,
+
+    /// Most images accepted in one request.
+    pub max_images_per_request: usize,
+
+    /// Generations tried per choice before output that fails
+    /// `response_format` validation is reported as an error.
+    pub structured_output_attempts: u32,
+
+    /// How long a completed response is replayed for a repeated
+    /// `Idempotency-Key`.
+    pub idempotency_ttl_secs: u64,
+
+    /// Idempotency keys remembered at once; the least recently used are
+    /// forgotten first.
+    pub idempotency_capacity: usize,
+
+    /// Response cache for temperature-0 generations: "off", "memory" or "disk".
+    pub response_cache: String,
+
+    /// Directory of the "disk" response cache.
+    pub response_cache_dir: Option<String>,
+
+    /// Entries kept by the response cache. The "memory" cache evicts the
+    /// least recently used entries first, the "disk" cache the oldest.
+    pub response_cache_capacity: usize,
+
+    pub response_cache_ttl_secs: u64,
+
+    /// Price per 1000 tokens in micro-USDC for responses served from the
+    /// cache, instead of the tier price.
+    pub cache_hit_price_microusdc_per_1k: u64,
+
+    /// Share one node dispatch among identical temperature-0 requests (same
+    /// tier, prompt and parameters) in flight at the same time.
+    pub coalesce_requests: bool,
+
+    /// How long a generate request waits to be batched with others for
+    /// the same node, tier and protocol; 0 disables batching. Only nodes
+    /// advertising `batch_generate` receive batches.
+    pub batch_window_ms: u64,
+
+    /// Largest batch sent to a node; a full batch is sent immediately.
+    pub max_batch_size: usize,
+
+    /// Generate requests in flight per tier, e.g. { PRO = 32 }; tiers
+    /// without an entry are unlimited. Excess requests wait in the
+    /// admission queue.
+    pub tier_concurrency: BTreeMap<Tier, usize>,
+
+    /// Generate calls in flight per node; 0 means unlimited.
+    pub node_concurrency: usize,
+
+    /// Per-node overrides of `node_concurrency`, by URL as listed in
+    /// `node_urls`.
+    pub node_concurrency_limits: BTreeMap<String, usize>,
+
+    /// Requests allowed to wait for admission; beyond this they get 429.
+    pub admission_queue_size: usize,
+
+    /// Longest a request may wait for admission or a node slot; requests
+    /// expected to wait longer get 503 right away.
+    pub admission_max_wait_ms: u64,
+
+    /// Tenants by name, e.g. { acme = { api_keys = ["..."], priority = 10 } }.
+    pub tenants: BTreeMap<String, TenantSpec>,
+
+    /// Priority added for an `x-request-class` header value,
+    /// e.g. { interactive = 10, batch = -10 }.
+    pub request_classes: BTreeMap<String, i32>,
+
+    /// Policy system prompts for chat requests, applied in order to the
+    /// tenants and tiers they name.
+    pub system_prompts: Vec<SystemPromptSpec>,
+
+    /// On SIGTERM/SIGINT, how long in-flight requests may take to finish
+    /// before they are cancelled and the server exits.
+    pub shutdown_grace_secs: u64,
 }
 
 impl Default for AppConfig {
@@ -36,11 +215,77 @@ impl Default for AppConfig {
             node_urls: vec!["http://127.0.0.1:8080".to_string()],
             default_tier: Tier::Standard,
             max_cost_microusdc: 0,
+            receipts_dir: None,
+            keystore_path: None,
+            settlement_chain_id: 8453,
+            settlement_contract: None,
+            tokenizer: "approx".to_string(),
+            tokenizer_files: BTreeMap::new(),
+            tokenizer_pattern: None,
+            chat_template: "plain".to_string(),
+            chat_template_files: BTreeMap::new(),
+            reconcile_tolerance: 0.10,
+            reconcile_min_samples: 20,
+            tier_specs: default_tier_specs(),
+            model_aliases: BTreeMap::new(),
+            health_check_interval_secs: 15,
+            node_timeout_secs: 60,
+            max_image_bytes: 5 * 1024 * 1024,
+            max_images_per_request: 8,
+            structured_output_attempts: 3,
+            idempotency_ttl_secs: 24 * 60 * 60,
+            idempotency_capacity: 10_000,
+            response_cache: "off".to_string(),
+            response_cache_dir: None,
+            response_cache_capacity: 1024,
+            response_cache_ttl_secs: 60 * 60,
+            cache_hit_price_microusdc_per_1k: 0,
+            coalesce_requests: true,
+            batch_window_ms: 5,
+            max_batch_size: 8,
+            tier_concurrency: BTreeMap::new(),
+            node_concurrency: 0,
+            node_concurrency_limits: BTreeMap::new(),
+            admission_queue_size: 256,
+            admission_max_wait_ms: 10_000,
+            tenants: BTreeMap::new(),
+            request_classes: BTreeMap::new(),
+            system_prompts: Vec::new(),
+            shutdown_grace_secs: 30,
         }
     }
 }
 
+fn default_tier_specs() -> BTreeMap<Tier, TierSpec> {
+    let spec = |context_window, price_microusdc_per_1k, multimodal| TierSpec {
+        context_window,
+        price_microusdc_per_1k,
+        multimodal,
+        chat_template: None,
+    };
+    BTreeMap::from([
+        (Tier::Nano, spec(8_192, 50, false)),
+        (Tier::Standard, spec(32_768, 200, false)),
+        (Tier::Pro, spec(128_000, 1_000, true)),
+        (Tier::Max, spec(200_000, 3_000, true)),
+    ])
+}
+
 impl AppConfig {
+    pub fn image_limits(&self) -> ImageLimits {
+        ImageLimits {
+            max_bytes: self.max_image_bytes,
+            max_images: self.max_images_per_request,
+        }
+    }
+
+    pub fn tier_spec(&self, tier: Tier) -> TierSpec {
+        self.tier_specs
+            .get(&tier)
+            .cloned()
+            .unwrap_or_else(|| default_tier_specs()[&tier].clone())
+    }
+
     pub fn load() -> anyhow::Result<Self> {
         // Supports:
         // - auria.toml / auria.json (optional)
@@ -49,11 +294,18 @@ impl AppConfig {
         //   AURIA_NODE_URLS (comma-separated)
         //   AURIA_DEFAULT_TIER
         //   AURIA_MAX_COST_MICROUSDC
-        let fig = Figment::from(Serialized::from(AppConfig::default(), "auria"))
+        //   AURIA_RECEIPTS_DIR
+        //   AURIA_KEYSTORE_PATH
+        //   AURIA_SETTLEMENT_CHAIN_ID
+        //   AURIA_SETTLEMENT_CONTRACT
+        //   AURIA_TOKENIZER
+        let fig = Figment::from(Serialized::defaults(AppConfig::default()))
             .merge(Toml::file("auria.toml").nested())
             .merge(Json::file("auria.json").nested())
             .merge(
                 Env::prefixed("AURIA_")
+                    // Parsed explicitly below: comma lists and case-insensitive tiers.
+                    .ignore(&["node_urls", "default_tier"])
                     .map(|k| Uncased::new(k.as_str().to_ascii_lowercase()))
                     .split(","),
             );
@@ -80,6 +332,23 @@ impl AppConfig {
                 cfg.max_cost_microusdc = n;
             }
         }
+        if let Ok(v) = std::env::var("AURIA_RECEIPTS_DIR") {
+            cfg.receipts_dir = Some(v).filter(|s| !s.is_empty());
+        }
+        if let Ok(v) = std::env::var("AURIA_KEYSTORE_PATH") {
+            cfg.keystore_path = Some(v).filter(|s| !s.is_empty());
+        }
+        if let Ok(v) = std::env::var("AURIA_SETTLEMENT_CHAIN_ID") {
+            if let Ok(n) = v.parse::<u64>() {
+                cfg.settlement_chain_id = n;
+            }
+        }
+        if let Ok(v) = std::env::var("AURIA_SETTLEMENT_CONTRACT") {
+            cfg.settlement_contract = Some(v).filter(|s| !s.is_empty());
+        }
+        if let Ok(v) = std::env::var("AURIA_TOKENIZER") {
+            cfg.tokenizer = v;
+        }
 
         Ok(cfg)
     }
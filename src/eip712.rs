@@ -0,0 +1,323 @@
+// File: eip712.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     EIP-712 typed structured data hashing and secp256k1 signing,
+//     following eth_signTypedData_v4 semantics (no chain access).
+//
+use std::collections::{BTreeMap, BTreeSet};
+
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Typed data in the JSON shape accepted by `eth_signTypedData_v4`.
+/// `types` must include `EIP712Domain`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+/// Ethereum-style recoverable signature; `v` is 27 or 28.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthSignature {
+    pub r: String,
+    pub s: String,
+    pub v: u8,
+}
+
+impl EthSignature {
+    /// 65-byte `r || s || v` encoding as accepted by `ECDSA.recover`.
+    pub fn to_hex(&self) -> String {
+        format!(
+            "0x{}{}{:02x}",
+            self.r.trim_start_matches("0x"),
+            self.s.trim_start_matches("0x"),
+            self.v
+        )
+    }
+}
+
+impl TypedData {
+    pub fn encode_type(&self, primary: &str) -> anyhow::Result<String> {
+        if !self.types.contains_key(primary) {
+            anyhow::bail!("unknown type {}", primary);
+        }
+        let mut deps = BTreeSet::new();
+        self.collect_deps(primary, &mut deps)?;
+        deps.remove(primary);
+
+        let mut out = String::new();
+        for name in std::iter::once(primary).chain(deps.iter().map(String::as_str)) {
+            let fields = &self.types[name];
+            let params: Vec<String> = fields
+                .iter()
+                .map(|f| format!("{} {}", f.ty, f.name))
+                .collect();
+            out.push_str(&format!("{}({})", name, params.join(",")));
+        }
+        Ok(out)
+    }
+
+    pub fn type_hash(&self, primary: &str) -> anyhow::Result<[u8; 32]> {
+        Ok(keccak256(self.encode_type(primary)?.as_bytes()))
+    }
+
+    pub fn hash_struct(&self, ty: &str, value: &Value) -> anyhow::Result<[u8; 32]> {
+        let fields = self
+            .types
+            .get(ty)
+            .ok_or_else(|| anyhow::anyhow!("unknown type {}", ty))?;
+        let obj = value
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("{} must be an object", ty))?;
+
+        let mut enc = Vec::with_capacity(32 * (fields.len() + 1));
+        enc.extend_from_slice(&self.type_hash(ty)?);
+        for f in fields {
+            let v = obj
+                .get(&f.name)
+                .ok_or_else(|| anyhow::anyhow!("{}.{} is missing", ty, f.name))?;
+            enc.extend_from_slice(&self.encode_value(&f.ty, v)?);
+        }
+        Ok(keccak256(&enc))
+    }
+
+    pub fn domain_separator(&self) -> anyhow::Result<[u8; 32]> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    /// `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`.
+    pub fn signing_hash(&self) -> anyhow::Result<[u8; 32]> {
+        let mut buf = Vec::with_capacity(66);
+        buf.extend_from_slice(&[0x19, 0x01]);
+        buf.extend_from_slice(&self.domain_separator()?);
+        buf.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        Ok(keccak256(&buf))
+    }
+
+    pub fn sign(&self, key: &SigningKey) -> anyhow::Result<EthSignature> {
+        let (sig, recid) = key.sign_prehash_recoverable(&self.signing_hash()?)?;
+        let bytes = sig.to_bytes();
+        Ok(EthSignature {
+            r: format!("0x{}", hex::encode(&bytes[..32])),
+            s: format!("0x{}", hex::encode(&bytes[32..])),
+            v: 27 + recid.to_byte(),
+        })
+    }
+
+    fn collect_deps(&self, ty: &str, out: &mut BTreeSet<String>) -> anyhow::Result<()> {
+        let base = strip_array(ty);
+        if out.contains(base) || !self.types.contains_key(base) {
+            return Ok(());
+        }
+        out.insert(base.to_string());
+        for f in &self.types[base] {
+            self.collect_deps(&f.ty, out)?;
+        }
+        Ok(())
+    }
+
+    fn encode_value(&self, ty: &str, v: &Value) -> anyhow::Result<[u8; 32]> {
+        if let Some(inner) = array_element(ty) {
+            let items = v
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("{} must be an array", ty))?;
+            let mut enc = Vec::with_capacity(32 * items.len());
+            for item in items {
+                enc.extend_from_slice(&self.encode_value(inner, item)?);
+            }
+            return Ok(keccak256(&enc));
+        }
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, v);
+        }
+
+        match ty {
+            "string" => Ok(keccak256(as_str(ty, v)?.as_bytes())),
+            "bytes" => Ok(keccak256(&decode_hex(as_str(ty, v)?)?)),
+            "bool" => {
+                let b = v
+                    .as_bool()
+                    .ok_or_else(|| anyhow::anyhow!("bool expected"))?;
+                let mut out = [0u8; 32];
+                out[31] = b as u8;
+                Ok(out)
+            }
+            "address" => {
+                let raw = decode_hex(as_str(ty, v)?)?;
+                if raw.len() != 20 {
+                    anyhow::bail!("address must be 20 bytes");
+                }
+                let mut out = [0u8; 32];
+                out[12..].copy_from_slice(&raw);
+                Ok(out)
+            }
+            _ if ty.starts_with("bytes") => {
+                let n: usize = ty[5..]
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("unsupported type {}", ty))?;
+                let raw = decode_hex(as_str(ty, v)?)?;
+                if n == 0 || n > 32 || raw.len() != n {
+                    anyhow::bail!("{} value must be {} bytes", ty, n);
+                }
+                let mut out = [0u8; 32];
+                out[..n].copy_from_slice(&raw);
+                Ok(out)
+            }
+            _ if ty.starts_with("uint") => encode_uint(&ty[4..], v),
+            _ if ty.starts_with("int") => encode_int(&ty[3..], v),
+            _ => anyhow::bail!("unsupported type {}", ty),
+        }
+    }
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Ethereum address (`0x`-prefixed, lowercase hex) for a secp256k1 public key.
+pub fn address_of(key: &VerifyingKey) -> String {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    format!("0x{}", hex::encode(&hash[12..]))
+}
+
+fn strip_array(ty: &str) -> &str {
+    ty.find('[').map_or(ty, |i| &ty[..i])
+}
+
+fn array_element(ty: &str) -> Option<&str> {
+    if ty.ends_with(']') {
+        ty.rfind('[').map(|i| &ty[..i])
+    } else {
+        None
+    }
+}
+
+fn as_str<'a>(ty: &str, v: &'a Value) -> anyhow::Result<&'a str> {
+    v.as_str()
+        .ok_or_else(|| anyhow::anyhow!("{} value must be a string", ty))
+}
+
+fn decode_hex(s: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(hex::decode(s.trim_start_matches("0x"))?)
+}
+
+fn bit_width(suffix: &str) -> anyhow::Result<u32> {
+    let bits = if suffix.is_empty() {
+        256
+    } else {
+        suffix
+            .parse()
+            .map_err(|_| anyhow::anyhow!("bad integer width"))?
+    };
+    if bits == 0 || bits > 256 || bits % 8 != 0 {
+        anyhow::bail!("bad integer width {}", bits);
+    }
+    Ok(bits)
+}
+
+/// Parses a JSON number, decimal string or `0x` hex string into a
+/// 256-bit big-endian magnitude plus a sign flag.
+fn parse_integer(v: &Value) -> anyhow::Result<(bool, [u8; 32])> {
+    let text = match v {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => anyhow::bail!("integer expected"),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+
+    let mut out = [0u8; 32];
+    if let Some(h) = digits.strip_prefix("0x") {
+        let raw = hex::decode(if h.len() % 2 == 1 {
+            format!("0{}", h)
+        } else {
+            h.to_string()
+        })?;
+        if raw.len() > 32 {
+            anyhow::bail!("integer overflows 256 bits");
+        }
+        out[32 - raw.len()..].copy_from_slice(&raw);
+        return Ok((negative, out));
+    }
+
+    if digits.is_empty() {
+        anyhow::bail!("empty integer");
+    }
+    for c in digits.chars() {
+        let d = c
+            .to_digit(10)
+            .ok_or_else(|| anyhow::anyhow!("invalid integer {}", text))?;
+        let mut carry = d;
+        for byte in out.iter_mut().rev() {
+            let x = (*byte as u32) * 10 + carry;
+            *byte = x as u8;
+            carry = x >> 8;
+        }
+        if carry != 0 {
+            anyhow::bail!("integer overflows 256 bits");
+        }
+    }
+    Ok((negative, out))
+}
+
+fn significant_bits(mag: &[u8; 32]) -> u32 {
+    match mag.iter().position(|b| *b != 0) {
+        Some(i) => (32 - i as u32) * 8 - mag[i].leading_zeros(),
+        None => 0,
+    }
+}
+
+fn encode_uint(suffix: &str, v: &Value) -> anyhow::Result<[u8; 32]> {
+    let bits = bit_width(suffix)?;
+    let (negative, mag) = parse_integer(v)?;
+    if negative && significant_bits(&mag) > 0 {
+        anyhow::bail!("uint{} cannot be negative", bits);
+    }
+    if significant_bits(&mag) > bits {
+        anyhow::bail!("value overflows uint{}", bits);
+    }
+    Ok(mag)
+}
+
+fn encode_int(suffix: &str, v: &Value) -> anyhow::Result<[u8; 32]> {
+    let bits = bit_width(suffix)?;
+    let (negative, mag) = parse_integer(v)?;
+    let limit = significant_bits(&mag);
+    // Two's complement range: [-2^(bits-1), 2^(bits-1) - 1].
+    let is_min = negative && limit == bits && mag.iter().map(|b| b.count_ones()).sum::<u32>() == 1;
+    if limit >= bits && !is_min {
+        anyhow::bail!("value overflows int{}", bits);
+    }
+    if !negative {
+        return Ok(mag);
+    }
+
+    let mut out = mag;
+    for b in out.iter_mut() {
+        *b = !*b;
+    }
+    for b in out.iter_mut().rev() {
+        let (x, overflow) = b.overflowing_add(1);
+        *b = x;
+        if !overflow {
+            break;
+        }
+    }
+    Ok(out)
+}
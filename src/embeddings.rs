@@ -0,0 +1,61 @@
+// File: embeddings.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Post-processing of node embeddings for the OpenAI-compatible
+//     embeddings endpoint: dimension truncation and output encoding.
+//
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::models::EmbeddingVector;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncodingFormat {
+    Float,
+    Base64,
+}
+
+impl EncodingFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "float" => Some(Self::Float),
+            "base64" => Some(Self::Base64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Float => "float",
+            Self::Base64 => "base64",
+        }
+    }
+}
+
+/// Keeps the first `dims` components and re-normalises to unit length,
+/// matching OpenAI's `dimensions` semantics for Matryoshka-style models.
+pub fn truncate(mut v: Vec<f32>, dims: u32) -> anyhow::Result<Vec<f32>> {
+    let dims = dims as usize;
+    if dims == 0 || dims > v.len() {
+        anyhow::bail!("dimensions must be between 1 and {}", v.len());
+    }
+    v.truncate(dims);
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v.iter_mut() {
+            *x /= norm;
+        }
+    }
+    Ok(v)
+}
+
+pub fn encode(v: Vec<f32>, format: EncodingFormat) -> EmbeddingVector {
+    match format {
+        EncodingFormat::Float => EmbeddingVector::Float(v),
+        EncodingFormat::Base64 => {
+            let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+            EmbeddingVector::Base64(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// File: error.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Typed agent errors and their OpenAI-compatible HTTP representation
+//     (status code, error.type, error.code and request id).
+//
+use axum::{
+    extract::rejection::JsonRejection,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::batch::BatchError;
+
+/// Failure of an agent request, classified so clients can tell caller
+/// mistakes (do not retry) from capacity and upstream problems (retry).
+#[derive(Clone, Debug, Error)]
+pub enum AgentError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    /// An `Idempotency-Key` reused with a different request body.
+    #[error("{0}")]
+    IdempotencyMismatch(String),
+    #[error("{0}")]
+    Authentication(String),
+    #[error("{0}")]
+    Permission(String),
+    /// Load shed before reaching a node; the second field is the
+    /// `Retry-After` estimate in seconds, when the agent has one.
+    #[error("{0}")]
+    RateLimit(String, Option<u64>),
+    #[error("{0}")]
+    InsufficientQuota(String),
+    /// No node can take the request now; `Retry-After` as for `RateLimit`.
+    #[error("{0}")]
+    NoCapacity(String, Option<u64>),
+    #[error("{0}")]
+    UpstreamTimeout(String),
+    #[error("{0}")]
+    UpstreamError(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl AgentError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            Self::IdempotencyMismatch(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Authentication(_) => StatusCode::UNAUTHORIZED,
+            Self::Permission(_) => StatusCode::FORBIDDEN,
+            Self::RateLimit(..) | Self::InsufficientQuota(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::NoCapacity(..) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::UpstreamTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            Self::UpstreamError(_) => StatusCode::BAD_GATEWAY,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// OpenAI `error.type`.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest(_) | Self::IdempotencyMismatch(_) => "invalid_request_error",
+            Self::Authentication(_) => "authentication_error",
+            Self::Permission(_) => "permission_error",
+            Self::RateLimit(..) => "rate_limit_error",
+            Self::InsufficientQuota(_) => "insufficient_quota",
+            Self::NoCapacity(..)
+            | Self::UpstreamTimeout(_)
+            | Self::UpstreamError(_)
+            | Self::Internal(_) => "server_error",
+        }
+    }
+
+    /// OpenAI `error.code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest(_) => "invalid_request",
+            Self::IdempotencyMismatch(_) => "idempotency_key_reused",
+            Self::Authentication(_) => "invalid_api_key",
+            Self::Permission(_) => "permission_denied",
+            Self::RateLimit(..) => "rate_limit_exceeded",
+            Self::InsufficientQuota(_) => "insufficient_quota",
+            Self::NoCapacity(..) => "no_capacity",
+            Self::UpstreamTimeout(_) => "upstream_timeout",
+            Self::UpstreamError(_) => "upstream_error",
+            Self::Internal(_) => "internal_error",
+        }
+    }
+
+    /// Whether retrying the same request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimit(..)
+                | Self::NoCapacity(..)
+                | Self::UpstreamTimeout(_)
+                | Self::UpstreamError(_)
+        )
+    }
+
+    /// `Retry-After` seconds for load shedding (queue full or saturated):
+    /// the agent's estimate, or 1 without one.
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::RateLimit(_, secs) | Self::NoCapacity(_, secs) => Some(secs.unwrap_or(1)),
+            _ => None,
+        }
+    }
+
+    /// A failed node call. Timeouts stay timeouts; anything else the node
+    /// got wrong (error status, malformed or inconsistent body) is an
+    /// upstream error, which clients may retry.
+    pub fn from_node(e: anyhow::Error) -> Self {
+        match Self::from(e) {
+            Self::Internal(m) => Self::UpstreamError(m),
+            other => other,
+        }
+    }
+
+    pub fn with_request_id(self, request_id: impl Into<String>) -> ApiError {
+        ApiError {
+            error: self,
+            request_id: request_id.into(),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AgentError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::UpstreamTimeout(format!("node timed out: {}", e))
+        } else {
+            Self::UpstreamError(format!("node request failed: {}", e))
+        }
+    }
+}
+
+impl From<JsonRejection> for AgentError {
+    fn from(e: JsonRejection) -> Self {
+        Self::InvalidRequest(e.body_text())
+    }
+}
+
+/// Node calls surface reqwest errors through anyhow; anything else that
+/// reaches this point is unexpected.
+impl From<anyhow::Error> for AgentError {
+    fn from(e: anyhow::Error) -> Self {
+        let e = match e.downcast::<reqwest::Error>() {
+            Ok(re) => return re.into(),
+            Err(e) => e,
+        };
+        match e.downcast::<BatchError>() {
+            Ok(BatchError::Timeout(m)) => Self::UpstreamTimeout(m),
+            Ok(BatchError::Upstream(m)) => Self::UpstreamError(m),
+            Err(e) => Self::Internal(e.to_string()),
+        }
+    }
+}
+
+/// An [`AgentError`] tagged with the id of the request that failed,
+/// rendered as an OpenAI-shaped error body plus `x-request-id`.
+#[derive(Debug)]
+pub struct ApiError {
+    pub error: AgentError,
+    pub request_id: String,
+}
+
+/// OpenAI-shaped error body.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorDetail {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub param: Option<String>,
+    pub code: Option<String>,
+    /// Also sent as the `x-request-id` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorResponse {
+            error: ErrorDetail {
+                message: self.error.to_string(),
+                kind: self.error.error_type().to_string(),
+                param: None,
+                code: Some(self.error.code().to_string()),
+                request_id: Some(self.request_id.clone()),
+            },
+        };
+        let mut resp = (self.error.status(), Json(body)).into_response();
+        if let Ok(v) = HeaderValue::from_str(&self.request_id) {
+            resp.headers_mut().insert("x-request-id", v);
+        }
+        if let Some(secs) = self.error.retry_after() {
+            resp.headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        resp
+    }
+}
@@ -0,0 +1,205 @@
+// File: idempotency.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Idempotency-Key handling: the first request for a key runs,
+//     concurrent duplicates wait for it and later duplicates replay the
+//     stored response until it expires or is evicted.
+//
+use std::{
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use sha3::{Digest, Sha3_256};
+use tokio::sync::watch;
+
+use crate::error::AgentError;
+
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Keys tracked unless configured otherwise.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// How often the server drops expired responses.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Hex SHA3-256 of `bytes`. Used for request body fingerprints and to
+/// scope keys by API key without holding the API key itself.
+pub fn fingerprint(bytes: &[u8]) -> String {
+    hex::encode(Sha3_256::digest(bytes))
+}
+
+enum Slot<T> {
+    Pending {
+        fingerprint: String,
+        done: watch::Receiver<Option<T>>,
+    },
+    Done {
+        fingerprint: String,
+        response: T,
+        expires: Instant,
+    },
+}
+
+/// Responses by (scope, key), at most `capacity` of them: the least
+/// recently used keys are forgotten first. Only successful responses are
+/// stored; a failed first request releases the key so a retry runs again.
+pub struct IdempotencyStore<T> {
+    ttl: Duration,
+    slots: Mutex<LruCache<(String, String), Slot<T>>>,
+}
+
+pub enum Claim<'a, T> {
+    /// First request for the key: run it and `complete` the lease.
+    Run(Lease<'a, T>),
+    /// Stored response of an earlier identical request.
+    Replay(T),
+}
+
+impl<T: Clone> IdempotencyStore<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            ttl,
+            slots: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops expired responses.
+    pub fn sweep(&self) {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let expired: Vec<(String, String)> = slots
+            .iter()
+            .filter(|(_, s)| matches!(s, Slot::Done { expires, .. } if *expires <= now))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for k in expired {
+            slots.pop(&k);
+        }
+    }
+
+    /// Claims `key` within `scope` for a request whose body hashes to
+    /// `fingerprint`, waiting while an identical request is in flight.
+    pub async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim<'_, T>, AgentError> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(AgentError::InvalidRequest(format!(
+                "Idempotency-Key must be 1 to {} characters",
+                MAX_KEY_LEN
+            )));
+        }
+        let slot = (scope.to_string(), key.to_string());
+        loop {
+            let mut done = {
+                let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+                if matches!(slots.peek(&slot), Some(Slot::Done { expires, .. }) if *expires <= Instant::now())
+                {
+                    slots.pop(&slot);
+                }
+                match slots.get(&slot) {
+                    Some(Slot::Done {
+                        fingerprint: f,
+                        response,
+                        ..
+                    }) => {
+                        check_fingerprint(f, fingerprint)?;
+                        return Ok(Claim::Replay(response.clone()));
+                    }
+                    Some(Slot::Pending {
+                        fingerprint: f,
+                        done,
+                    }) => {
+                        check_fingerprint(f, fingerprint)?;
+                        done.clone()
+                    }
+                    None => {
+                        let (tx, rx) = watch::channel(None);
+                        slots.put(
+                            slot.clone(),
+                            Slot::Pending {
+                                fingerprint: fingerprint.to_string(),
+                                done: rx,
+                            },
+                        );
+                        return Ok(Claim::Run(Lease {
+                            store: self,
+                            slot,
+                            fingerprint: fingerprint.to_string(),
+                            tx,
+                            completed: false,
+                        }));
+                    }
+                }
+            };
+            // The sender is dropped without a value when the first request
+            // fails; claim again so one of the waiters runs it.
+            if let Ok(response) = done.wait_for(Option::is_some).await.map(|r| r.clone()) {
+                return Ok(Claim::Replay(response.expect("waited for a response")));
+            }
+        }
+    }
+}
+
+fn check_fingerprint(stored: &str, fingerprint: &str) -> Result<(), AgentError> {
+    if stored != fingerprint {
+        return Err(AgentError::IdempotencyMismatch(
+            "Idempotency-Key was already used with a different request body".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Exclusive right to run the request for a key. Dropping the lease
+/// without completing it releases the key.
+pub struct Lease<'a, T> {
+    store: &'a IdempotencyStore<T>,
+    slot: (String, String),
+    fingerprint: String,
+    tx: watch::Sender<Option<T>>,
+    completed: bool,
+}
+
+impl<T: Clone> Lease<'_, T> {
+    /// Stores `response` for replay and hands it to waiting duplicates.
+    pub fn complete(mut self, response: T) {
+        let mut slots = self.store.slots.lock().unwrap_or_else(|e| e.into_inner());
+        slots.put(
+            self.slot.clone(),
+            Slot::Done {
+                fingerprint: self.fingerprint.clone(),
+                response: response.clone(),
+                expires: Instant::now() + self.store.ttl,
+            },
+        );
+        self.tx.send_replace(Some(response));
+        self.completed = true;
+    }
+}
+
+impl<T> Drop for Lease<'_, T> {
+    fn drop(&mut self) {
+        if !self.completed {
+            let mut slots = self.store.slots.lock().unwrap_or_else(|e| e.into_inner());
+            slots.pop(&self.slot);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+// File: keystore.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Passphrase-encrypted keystore holding the agent identity keys
+//     (ed25519 for receipts, secp256k1 for settlement signing).
+//
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::receipts::now_unix;
+
+const KEYSTORE_VERSION: u32 = 1;
+
+/// Environment variable holding the keystore passphrase. The passphrase is
+/// deliberately not part of `AppConfig` so it never lands in config dumps.
+pub const PASSPHRASE_ENV: &str = "AURIA_KEYSTORE_PASSPHRASE";
+
+pub fn passphrase_from_env() -> anyhow::Result<String> {
+    std::env::var(PASSPHRASE_ENV).map_err(|_| anyhow::anyhow!("{} is not set", PASSPHRASE_ENV))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyAlgorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+impl KeyAlgorithm {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ed25519" => Some(KeyAlgorithm::Ed25519),
+            "secp256k1" => Some(KeyAlgorithm::Secp256k1),
+            _ => None,
+        }
+    }
+}
+
+/// A key as stored inside the encrypted keystore payload.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct StoredKey {
+    id: String,
+    algorithm: KeyAlgorithm,
+    secret: String,
+    created: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retired: Option<i64>,
+}
+
+/// Public view of a key, safe to print or serve over HTTP.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct PublicKeyInfo {
+    pub id: String,
+    pub algorithm: KeyAlgorithm,
+    /// Hex-encoded public key (compressed SEC1 for secp256k1).
+    pub public_key: String,
+    pub created: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retired: Option<i64>,
+}
+
+/// On-disk envelope: argon2id-derived key, XChaCha20-Poly1305 ciphertext.
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct KeyRing {
+    keys: Vec<StoredKey>,
+}
+
+pub struct Keystore {
+    path: PathBuf,
+    passphrase: String,
+    ring: KeyRing,
+}
+
+impl Keystore {
+    /// Opens an existing keystore, or starts an empty one if `path` does not exist yet.
+    pub fn open_or_create(path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            return Self::open(path, passphrase);
+        }
+        Ok(Self {
+            path,
+            passphrase: passphrase.to_string(),
+            ring: KeyRing::default(),
+        })
+    }
+
+    pub fn open(path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file: EncryptedFile = serde_json::from_slice(&std::fs::read(&path)?)?;
+        if file.version != KEYSTORE_VERSION || file.kdf != "argon2id" {
+            anyhow::bail!(
+                "unsupported keystore format (version {}, kdf {})",
+                file.version,
+                file.kdf
+            );
+        }
+
+        let salt = hex::decode(&file.salt)?;
+        let nonce = hex::decode(&file.nonce)?;
+        if nonce.len() != 24 {
+            anyhow::bail!("keystore nonce must be 24 bytes");
+        }
+        let cipher = cipher_for(passphrase, &salt)?;
+        let plaintext = cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                hex::decode(&file.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| anyhow::anyhow!("keystore decryption failed (wrong passphrase?)"))?;
+
+        Ok(Self {
+            path,
+            passphrase: passphrase.to_string(),
+            ring: serde_json::from_slice(&plaintext)?,
+        })
+    }
+
+    /// Re-encrypts the key ring under a fresh salt and nonce and writes it atomically.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let salt = random_bytes::<16>()?;
+        let nonce = random_bytes::<24>()?;
+        let cipher = cipher_for(&self.passphrase, &salt)?;
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                serde_json::to_vec(&self.ring)?.as_slice(),
+            )
+            .map_err(|_| anyhow::anyhow!("keystore encryption failed"))?;
+
+        let file = EncryptedFile {
+            version: KEYSTORE_VERSION,
+            kdf: "argon2id".to_string(),
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        // Created owner-only, so the ciphertext is never readable by others,
+        // even briefly; a leftover from an interrupted save is replaced.
+        let _ = std::fs::remove_file(&tmp);
+        let mut opts = std::fs::OpenOptions::new();
+        opts.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+        opts.open(&tmp)?
+            .write_all(&serde_json::to_vec_pretty(&file)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    /// Adds a freshly generated key. Fails if one is already active for `algorithm`.
+    pub fn generate(&mut self, algorithm: KeyAlgorithm) -> anyhow::Result<PublicKeyInfo> {
+        let secret = loop {
+            let bytes = random_bytes::<32>()?;
+            // Rejection-sample the (astronomically rare) invalid secp256k1 scalars.
+            if algorithm == KeyAlgorithm::Ed25519 || k256::SecretKey::from_slice(&bytes).is_ok() {
+                break bytes;
+            }
+        };
+        self.insert(algorithm, &secret)
+    }
+
+    /// Imports an existing 32-byte secret. Fails if one is already active for `algorithm`.
+    pub fn import(
+        &mut self,
+        algorithm: KeyAlgorithm,
+        secret_hex: &str,
+    ) -> anyhow::Result<PublicKeyInfo> {
+        let secret: [u8; 32] = hex::decode(secret_hex.trim().trim_start_matches("0x"))?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("secret key must be 32 bytes"))?;
+        self.insert(algorithm, &secret)
+    }
+
+    /// Retires the active key for `algorithm` and generates its replacement.
+    /// Retired keys are kept so previously signed receipts stay verifiable.
+    pub fn rotate(&mut self, algorithm: KeyAlgorithm) -> anyhow::Result<PublicKeyInfo> {
+        let now = now_unix();
+        for k in self
+            .ring
+            .keys
+            .iter_mut()
+            .filter(|k| k.algorithm == algorithm && k.retired.is_none())
+        {
+            k.retired = Some(now);
+        }
+        self.generate(algorithm)
+    }
+
+    pub fn public_keys(&self) -> anyhow::Result<Vec<PublicKeyInfo>> {
+        self.ring.keys.iter().map(public_info).collect()
+    }
+
+    /// Signing identity built from the currently active keys.
+    pub fn identity(&self) -> anyhow::Result<AgentIdentity> {
+        let ed25519 = match self.active(KeyAlgorithm::Ed25519) {
+            Some(k) => Some((k.id.clone(), SigningKey::from_bytes(&decode_secret(k)?))),
+            None => None,
+        };
+        let secp256k1 = match self.active(KeyAlgorithm::Secp256k1) {
+            Some(k) => Some((
+                k.id.clone(),
+                k256::ecdsa::SigningKey::from_slice(&decode_secret(k)?)?,
+            )),
+            None => None,
+        };
+        Ok(AgentIdentity {
+            ed25519,
+            secp256k1,
+            public_keys: self.public_keys()?,
+        })
+    }
+
+    fn active(&self, algorithm: KeyAlgorithm) -> Option<&StoredKey> {
+        self.ring
+            .keys
+            .iter()
+            .rev()
+            .find(|k| k.algorithm == algorithm && k.retired.is_none())
+    }
+
+    fn insert(
+        &mut self,
+        algorithm: KeyAlgorithm,
+        secret: &[u8; 32],
+    ) -> anyhow::Result<PublicKeyInfo> {
+        if self.active(algorithm).is_some() {
+            anyhow::bail!(
+                "an active {:?} key already exists; rotate it instead",
+                algorithm
+            );
+        }
+        let key = StoredKey {
+            id: format!("key_{}", uuid::Uuid::new_v4().simple()),
+            algorithm,
+            secret: hex::encode(secret),
+            created: now_unix(),
+            retired: None,
+        };
+        let info = public_info(&key)?;
+        self.ring.keys.push(key);
+        Ok(info)
+    }
+}
+
+/// Active signing keys loaded from the keystore at startup.
+#[derive(Clone)]
+pub struct AgentIdentity {
+    ed25519: Option<(String, SigningKey)>,
+    secp256k1: Option<(String, k256::ecdsa::SigningKey)>,
+    public_keys: Vec<PublicKeyInfo>,
+}
+
+impl AgentIdentity {
+    /// Hex-encoded public key of the active ed25519 key, used as the receipt signer.
+    pub fn ed25519_public_hex(&self) -> Option<String> {
+        self.ed25519
+            .as_ref()
+            .map(|(_, k)| hex::encode(k.verifying_key().to_bytes()))
+    }
+
+    pub fn sign_ed25519(&self, msg: &[u8]) -> Option<String> {
+        self.ed25519
+            .as_ref()
+            .map(|(_, k)| hex::encode(k.sign(msg).to_bytes()))
+    }
+
+    pub fn secp256k1(&self) -> Option<&k256::ecdsa::SigningKey> {
+        self.secp256k1.as_ref().map(|(_, k)| k)
+    }
+
+    /// All known public keys, including retired ones.
+    pub fn public_keys(&self) -> &[PublicKeyInfo] {
+        &self.public_keys
+    }
+}
+
+fn public_info(k: &StoredKey) -> anyhow::Result<PublicKeyInfo> {
+    let secret = decode_secret(k)?;
+    let public_key = match k.algorithm {
+        KeyAlgorithm::Ed25519 => {
+            hex::encode(SigningKey::from_bytes(&secret).verifying_key().to_bytes())
+        }
+        KeyAlgorithm::Secp256k1 => {
+            let sk = k256::ecdsa::SigningKey::from_slice(&secret)?;
+            hex::encode(sk.verifying_key().to_encoded_point(true).as_bytes())
+        }
+    };
+    Ok(PublicKeyInfo {
+        id: k.id.clone(),
+        algorithm: k.algorithm,
+        public_key,
+        created: k.created,
+        retired: k.retired,
+    })
+}
+
+fn decode_secret(k: &StoredKey) -> anyhow::Result<[u8; 32]> {
+    hex::decode(&k.secret)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("corrupt secret for key {}", k.id))
+}
+
+fn cipher_for(passphrase: &str, salt: &[u8]) -> anyhow::Result<XChaCha20Poly1305> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("key derivation failed: {}", e))?;
+    Ok(XChaCha20Poly1305::new(&key.into()))
+}
+
+fn random_bytes<const N: usize>() -> anyhow::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    getrandom::getrandom(&mut buf).map_err(|e| anyhow::anyhow!("os rng failed: {}", e))?;
+    Ok(buf)
+}
@@ -4,13 +4,31 @@
 //     Core library entry point for the AURIA agent framework.
 //     Exports all public modules and re-exports main types.
 //
+pub mod admission;
+pub mod agent;
+pub mod anthropic;
+pub mod api;
+pub mod batch;
+pub mod cache;
+pub mod chat_template;
+pub mod coalesce;
 pub mod config;
+pub mod eip712;
+pub mod embeddings;
+pub mod error;
+pub mod idempotency;
+pub mod keystore;
 pub mod models;
-pub mod policy;
+pub mod multimodal;
 pub mod node_client;
+pub mod policy;
+pub mod receipts;
+pub mod reconcile;
 pub mod routing;
-pub mod agent;
-pub mod api;
+pub mod settlement;
+pub mod structured;
 pub mod telemetry;
+pub mod tokenizer;
+pub mod tools;
 
 pub use agent::AuriaAgent;
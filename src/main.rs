@@ -2,15 +2,29 @@
 // Copyright (c) 2026 AURIA Developers and Contributors
 // Description:
 //     Command-line interface for the AURIA agent controller.
-//     Supports serve, config inspection, and node connectivity checks.
+//     Supports serve, config inspection, node connectivity checks,
+//     receipt ledger inspection/export, and keystore management.
 //
-use clap::{Parser, Subcommand};
-use tracing::{info};
+use std::path::PathBuf;
 
-use auria::{config::AppConfig, AuriaAgent};
+use clap::{Parser, Subcommand, ValueEnum};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tracing::info;
+
+use auria::{
+    config::AppConfig,
+    keystore::{self, KeyAlgorithm, Keystore},
+    receipts::{self, Ledger, OutboxEntry, SettlementStatus, VerifyOutcome},
+    settlement::{SettlementBatch, SettlementDomain},
+    AuriaAgent,
+};
 
 #[derive(Parser, Debug)]
-#[command(name="auria", version, about="AURIA Agent (controller) — production skeleton")]
+#[command(
+    name = "auria",
+    version,
+    about = "AURIA Agent (controller) — production skeleton"
+)]
 struct Cli {
     #[command(subcommand)]
     cmd: Command,
@@ -28,6 +42,99 @@ enum Command {
     Config,
     /// Perform a lightweight connectivity check against configured Auria Nodes
     Check,
+    /// Inspect and export usage receipts from the local ledger
+    Receipts {
+        #[command(subcommand)]
+        cmd: ReceiptsCommand,
+    },
+    /// Manage the agent keystore
+    Keys {
+        #[command(subcommand)]
+        cmd: KeysCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum KeysCommand {
+    /// Generate the first key for an algorithm, creating the keystore if needed
+    Generate {
+        /// ed25519 or secp256k1
+        #[arg(long, default_value = "ed25519")]
+        algorithm: String,
+    },
+    /// Import an existing hex-encoded secret key read from stdin
+    Import {
+        /// ed25519 or secp256k1
+        #[arg(long, default_value = "ed25519")]
+        algorithm: String,
+    },
+    /// Show public keys, including retired ones
+    Show,
+    /// Retire the active key and generate a replacement
+    Rotate {
+        /// ed25519 or secp256k1
+        #[arg(long, default_value = "ed25519")]
+        algorithm: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ReceiptsCommand {
+    /// List the most recent receipts
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Show a receipt and its settlement status
+    Show { request_id: String },
+    /// Verify the signature on a receipt and that a trusted key made it
+    Verify {
+        request_id: String,
+        /// Trusted hex-encoded ed25519 public key (repeatable); defaults to
+        /// the keystore's active and retired keys
+        #[arg(long)]
+        signer: Vec<String>,
+    },
+    /// Export receipts in a time range
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Jsonl)]
+        format: ExportFormat,
+        /// Inclusive lower bound, RFC 3339 or unix seconds
+        #[arg(long)]
+        since: Option<String>,
+        /// Exclusive upper bound, RFC 3339 or unix seconds
+        #[arg(long)]
+        until: Option<String>,
+        /// Output file (default: stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Build and sign an EIP-712 settlement batch from pending receipts
+    Batch {
+        #[arg(long)]
+        epoch: u64,
+        /// Inclusive lower bound, RFC 3339 or unix seconds
+        #[arg(long)]
+        since: Option<String>,
+        /// Exclusive upper bound, RFC 3339 or unix seconds
+        #[arg(long)]
+        until: Option<String>,
+        /// Output file (default: stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Show the settlement outbox and status totals
+    Outbox {
+        /// Only show entries currently in this status
+        #[arg(long)]
+        status: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Jsonl,
 }
 
 #[tokio::main]
@@ -39,7 +146,9 @@ async fn main() -> anyhow::Result<()> {
 
     match cli.cmd {
         Command::Serve { bind } => {
-            if let Some(b) = bind { cfg.bind = b; }
+            if let Some(b) = bind {
+                cfg.bind = b;
+            }
             let agent = AuriaAgent::new(cfg.clone()).await?;
             info!("starting auria agent on {}", cfg.bind);
             auria::api::serve(cfg, agent).await?;
@@ -52,7 +161,231 @@ async fn main() -> anyhow::Result<()> {
             agent.check_nodes().await?;
             info!("node connectivity OK");
         }
+        Command::Receipts { cmd } => {
+            let dir = cfg
+                .receipts_dir
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("receipts_dir is not configured"))?;
+            run_receipts(&cfg, &Ledger::open(dir)?, cmd)?;
+        }
+        Command::Keys { cmd } => {
+            let path = cfg
+                .keystore_path
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("keystore_path is not configured"))?;
+            run_keys(path, cmd)?;
+        }
     }
 
     Ok(())
 }
+
+/// Ed25519 public keys in the keystore, active and retired.
+fn trusted_signers(cfg: &AppConfig) -> anyhow::Result<Vec<String>> {
+    let path = cfg.keystore_path.as_deref().ok_or_else(|| {
+        anyhow::anyhow!("keystore_path is not configured; pass the trusted key with --signer")
+    })?;
+    Ok(Keystore::open(path, &keystore::passphrase_from_env()?)?
+        .public_keys()?
+        .into_iter()
+        .filter(|k| k.algorithm == KeyAlgorithm::Ed25519)
+        .map(|k| k.public_key)
+        .collect())
+}
+
+fn run_receipts(cfg: &AppConfig, ledger: &Ledger, cmd: ReceiptsCommand) -> anyhow::Result<()> {
+    match cmd {
+        ReceiptsCommand::List { limit } => {
+            let all = ledger.receipts()?;
+            let status = ledger.settlement_status()?;
+            for r in all.iter().rev().take(limit) {
+                let st = status.get(&r.request_id).map_or("-", |e| e.status.as_str());
+                println!(
+                    "{}  {}  {:<8}  {:>6}  {:<9}  {}",
+                    format_time(r.created),
+                    r.request_id,
+                    r.tier.as_str(),
+                    r.total_tokens,
+                    st,
+                    r.node
+                );
+            }
+        }
+        ReceiptsCommand::Show { request_id } => {
+            let r = ledger
+                .find(&request_id)?
+                .ok_or_else(|| anyhow::anyhow!("no receipt for {}", request_id))?;
+            let status = ledger.settlement_status()?.remove(&request_id);
+            let out = serde_json::json!({ "receipt": r, "settlement": status });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        ReceiptsCommand::Verify { request_id, signer } => {
+            let r = ledger
+                .find(&request_id)?
+                .ok_or_else(|| anyhow::anyhow!("no receipt for {}", request_id))?;
+            let trusted = match signer.is_empty() {
+                true => trusted_signers(cfg)?,
+                false => signer,
+            };
+            match r.verify(&trusted)? {
+                VerifyOutcome::Valid => println!("{}: signature valid", request_id),
+                VerifyOutcome::Unsigned => println!("{}: unsigned", request_id),
+                VerifyOutcome::Invalid => anyhow::bail!("{}: signature INVALID", request_id),
+                VerifyOutcome::Untrusted => anyhow::bail!(
+                    "{}: signature UNTRUSTED: signer {} is not a known key",
+                    request_id,
+                    r.signer.unwrap_or_default()
+                ),
+            }
+        }
+        ReceiptsCommand::Export {
+            format,
+            since,
+            until,
+            out,
+        } => {
+            let since = since.as_deref().map(parse_time).transpose()?;
+            let until = until.as_deref().map(parse_time).transpose()?;
+            let selected = ledger.range(since, until)?;
+            let w: Box<dyn std::io::Write> = match out {
+                Some(p) => Box::new(std::io::BufWriter::new(std::fs::File::create(p)?)),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            match format {
+                ExportFormat::Csv => receipts::export_csv(w, &selected)?,
+                ExportFormat::Jsonl => receipts::export_jsonl(w, &selected)?,
+            }
+        }
+        ReceiptsCommand::Batch {
+            epoch,
+            since,
+            until,
+            out,
+        } => {
+            let domain = SettlementDomain {
+                chain_id: cfg.settlement_chain_id,
+                verifying_contract: cfg
+                    .settlement_contract
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("settlement_contract is not configured"))?,
+            };
+            let path = cfg
+                .keystore_path
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("keystore_path is not configured"))?;
+            let identity = Keystore::open(path, &keystore::passphrase_from_env()?)?.identity()?;
+            let key = identity.secp256k1().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "no active secp256k1 key; run `auria keys generate --algorithm secp256k1`"
+                )
+            })?;
+
+            let since = since.as_deref().map(parse_time).transpose()?;
+            let until = until.as_deref().map(parse_time).transpose()?;
+            let status = ledger.settlement_status()?;
+            let pending: Vec<_> = ledger
+                .range(since, until)?
+                .into_iter()
+                .filter(|r| {
+                    status.get(&r.request_id).map(|e| e.status) == Some(SettlementStatus::Pending)
+                })
+                .collect();
+            if pending.is_empty() {
+                anyhow::bail!("no pending receipts in range");
+            }
+
+            let signed = SettlementBatch::from_receipts(epoch, &pending).sign(&domain, key)?;
+            let json = serde_json::to_string_pretty(&signed)?;
+            match out {
+                Some(p) => std::fs::write(p, json)?,
+                None => println!("{}", json),
+            }
+
+            let now = receipts::now_unix();
+            for r in &pending {
+                ledger.update_status(&OutboxEntry {
+                    request_id: r.request_id.clone(),
+                    status: SettlementStatus::Batched,
+                    batch: Some(signed.digest.clone()),
+                    updated: now,
+                })?;
+            }
+        }
+        ReceiptsCommand::Outbox { status } => {
+            let filter = match status {
+                Some(s) => Some(
+                    SettlementStatus::parse(&s)
+                        .ok_or_else(|| anyhow::anyhow!("unknown status: {}", s))?,
+                ),
+                None => None,
+            };
+            let mut latest: Vec<_> = ledger.settlement_status()?.into_values().collect();
+            latest.sort_by_key(|e| e.updated);
+
+            let mut totals = std::collections::BTreeMap::new();
+            for e in &latest {
+                *totals.entry(e.status.as_str()).or_insert(0usize) += 1;
+                if filter.map(|f| f == e.status).unwrap_or(true) {
+                    println!(
+                        "{}  {}  {:<9}  {}",
+                        format_time(e.updated),
+                        e.request_id,
+                        e.status.as_str(),
+                        e.batch.as_deref().unwrap_or("-")
+                    );
+                }
+            }
+            for (st, n) in totals {
+                println!("{}: {}", st, n);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_keys(path: &str, cmd: KeysCommand) -> anyhow::Result<()> {
+    let passphrase = keystore::passphrase_from_env()?;
+    let info = match cmd {
+        KeysCommand::Generate { algorithm } => {
+            let mut ks = Keystore::open_or_create(path, &passphrase)?;
+            let info = ks.generate(parse_algorithm(&algorithm)?)?;
+            ks.save()?;
+            vec![info]
+        }
+        KeysCommand::Import { algorithm } => {
+            let mut secret = String::new();
+            std::io::stdin().read_line(&mut secret)?;
+            let mut ks = Keystore::open_or_create(path, &passphrase)?;
+            let info = ks.import(parse_algorithm(&algorithm)?, &secret)?;
+            ks.save()?;
+            vec![info]
+        }
+        KeysCommand::Show => Keystore::open(path, &passphrase)?.public_keys()?,
+        KeysCommand::Rotate { algorithm } => {
+            let mut ks = Keystore::open(path, &passphrase)?;
+            let info = ks.rotate(parse_algorithm(&algorithm)?)?;
+            ks.save()?;
+            vec![info]
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&info)?);
+    Ok(())
+}
+
+fn parse_algorithm(s: &str) -> anyhow::Result<KeyAlgorithm> {
+    KeyAlgorithm::parse(s).ok_or_else(|| anyhow::anyhow!("unknown key algorithm: {}", s))
+}
+
+fn parse_time(s: &str) -> anyhow::Result<i64> {
+    if let Ok(n) = s.parse::<i64>() {
+        return Ok(n);
+    }
+    Ok(OffsetDateTime::parse(s, &Rfc3339)?.unix_timestamp())
+}
+
+fn format_time(unix: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(unix)
+        .ok()
+        .and_then(|t| t.format(&Rfc3339).ok())
+        .unwrap_or_else(|| unix.to_string())
+}
@@ -4,19 +4,38 @@
 //     Data models for OpenAI-compatible API types including
 //     chat completions, messages, tiers, and usage tracking.
 //
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Tier {
     Nano,
+    #[default]
     Standard,
     Pro,
     Max,
 }
 
 impl Tier {
+    pub const ALL: [Tier; 4] = [Tier::Nano, Tier::Standard, Tier::Pro, Tier::Max];
+
     pub fn parse(s: &str) -> Option<Self> {
         match s.trim().to_ascii_uppercase().as_str() {
             "NANO" => Some(Tier::Nano),
@@ -26,24 +45,235 @@ impl Tier {
             _ => None,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tier::Nano => "NANO",
+            Tier::Standard => "STANDARD",
+            Tier::Pro => "PRO",
+            Tier::Max => "MAX",
+        }
+    }
+
+    /// Canonical model id, e.g. "AURIA:STANDARD".
+    pub fn model_id(&self) -> String {
+        format!("AURIA:{}", self.as_str())
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChatMessage {
     pub role: String,
-    pub content: String,
+    /// Null on assistant messages that only carry `tool_calls`.
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// Set on `tool` role messages: the call this message answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    pub fn new(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(MessageContent::Text(content.into())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+}
+
+/// Message content: a plain string or an array of typed parts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+impl MessageContent {
+    /// The string form, if this content is not an array of parts.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            MessageContent::Text(s) => Some(s),
+            MessageContent::Parts(_) => None,
+        }
+    }
+
+    pub fn parts(&self) -> Vec<ContentPart> {
+        match self {
+            MessageContent::Text(s) => vec![ContentPart::Text { text: s.clone() }],
+            MessageContent::Parts(p) => p.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImageUrl {
+    /// `https://` URL or a base64 `data:image/...` URL.
+    pub url: String,
+    /// `auto`, `low` or `high`; forwarded to the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Tool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionDef,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FunctionDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the arguments object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+
+/// `"none"`, `"auto"`, `"required"` or a specific function.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ToolChoice {
+    Mode(String),
+    Function {
+        #[serde(rename = "type")]
+        kind: String,
+        function: FunctionName,
+    },
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FunctionName {
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FunctionCall {
+    pub name: String,
+    /// JSON-encoded arguments, as in the OpenAI API.
+    pub arguments: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionRequest {
     /// Example: "AURIA:STANDARD"
     pub model: String,
     pub messages: Vec<ChatMessage>,
     pub max_tokens: Option<u32>,
+    /// Newer name for `max_tokens`; takes precedence when both are set.
+    pub max_completion_tokens: Option<u32>,
+    /// Number of choices to generate (default 1).
+    pub n: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// Stream `chat.completion.chunk` events over SSE.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    #[serde(flatten)]
+    pub sampling: SamplingParams,
+}
+
+/// OpenAI sampling parameters, forwarded to nodes unchanged. Ranges are
+/// validated by policy; support is checked against node capabilities.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SamplingParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Stop>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Token id (as a string, per the OpenAI API) to bias in [-100, 100].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<BTreeMap<String, f32>>,
+}
+
+impl SamplingParams {
+    /// Every parameter name a node may advertise support for.
+    pub const ALL: [&'static str; 7] = [
+        "temperature",
+        "top_p",
+        "stop",
+        "seed",
+        "presence_penalty",
+        "frequency_penalty",
+        "logit_bias",
+    ];
+
+    /// Names of the parameters set on this request.
+    pub fn requested(&self) -> Vec<&'static str> {
+        let set = [
+            self.temperature.is_some(),
+            self.top_p.is_some(),
+            self.stop.is_some(),
+            self.seed.is_some(),
+            self.presence_penalty.is_some(),
+            self.frequency_penalty.is_some(),
+            self.logit_bias.is_some(),
+        ];
+        Self::ALL
+            .iter()
+            .zip(set)
+            .filter(|(_, s)| *s)
+            .map(|(n, _)| *n)
+            .collect()
+    }
+}
+
+/// Stop sequences: a single string or a list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Stop {
+    One(String),
+    Many(Vec<String>),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl Stop {
+    pub fn sequences(&self) -> Vec<&str> {
+        match self {
+            Stop::One(s) => vec![s.as_str()],
+            Stop::Many(v) => v.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionResponse {
     pub id: String,
     pub created: i64,
@@ -52,18 +282,224 @@ pub struct ChatCompletionResponse {
     pub usage: Usage,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct Choice {
     pub index: u32,
     pub message: ChatMessage,
     pub finish_reason: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    Text,
+    JsonObject,
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamOptions {
+    /// Send a final chunk carrying usage and no choices.
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+/// One SSE event of a streamed chat completion.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChatCompletionChunk {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<ChunkChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChunkChoice {
+    pub index: u32,
+    pub delta: ChunkDelta,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChunkDelta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Streamed tool call. Calls are sent whole, so every field is present.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    /// Set when every choice was served from the response cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached: Option<bool>,
+}
+
+/// Prompt of a legacy completion: a single string or a batch of strings.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Prompt {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+impl Prompt {
+    pub fn len(&self) -> usize {
+        match self {
+            Prompt::Single(_) => 1,
+            Prompt::Batch(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            Prompt::Single(s) => vec![s],
+            Prompt::Batch(v) => v,
+        }
+    }
+}
+
+/// Legacy `POST /v1/completions` request.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CompletionRequest {
+    pub model: String,
+    pub prompt: Prompt,
+    /// Text that follows the completion (fill-in-the-middle).
+    pub suffix: Option<String>,
+    pub max_tokens: Option<u32>,
+    /// Completions per prompt (default 1).
+    pub n: Option<u32>,
+    #[serde(flatten)]
+    pub sampling: SamplingParams,
+    /// Prepend the prompt to each returned completion.
+    pub echo: Option<bool>,
+    /// Only 1 is supported; nodes do not return log probabilities to rank by.
+    pub best_of: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<CompletionChoice>,
+    pub usage: Usage,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CompletionChoice {
+    pub text: String,
+    pub index: u32,
+    /// Always null: nodes do not report log probabilities.
+    pub logprobs: Option<serde_json::Value>,
+    pub finish_reason: String,
+}
+
+/// `POST /v1/embeddings` request. Token-array inputs are not supported.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub input: Prompt,
+    /// Truncate vectors to this many leading dimensions (re-normalised).
+    pub dimensions: Option<u32>,
+    /// "float" (default) or "base64".
+    pub encoding_format: Option<String>,
+    pub user: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmbeddingResponse {
+    pub object: String,
+    pub data: Vec<Embedding>,
+    pub model: String,
+    pub usage: EmbeddingUsage,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Embedding {
+    pub object: String,
+    pub index: u32,
+    pub embedding: EmbeddingVector,
+}
+
+/// Floats, or base64 of the little-endian f32 bytes.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum EmbeddingVector {
+    Float(Vec<f32>),
+    Base64(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EmbeddingUsage {
+    pub prompt_tokens: u32,
+    pub total_tokens: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModelPricing {
+    /// Price per 1000 tokens (prompt and completion) in micro-USDC.
+    pub microusdc_per_1k_tokens: u64,
+}
+
+/// Entry of `GET /v1/models`, OpenAI-compatible with AURIA extensions.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModelInfo {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub owned_by: String,
+    pub tier: Tier,
+    /// Canonical tier model id when `id` is a configured alias.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_of: Option<String>,
+    pub context_window: u32,
+    /// Whether the tier accepts image inputs.
+    pub multimodal: bool,
+    pub pricing: ModelPricing,
+    /// Healthy nodes currently advertising this tier, not counting nodes
+    /// flagged for over-reporting tokens.
+    pub healthy_nodes: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ModelList {
+    pub object: String,
+    pub data: Vec<ModelInfo>,
 }
 
 pub fn new_id() -> String {
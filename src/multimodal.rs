@@ -0,0 +1,123 @@
+// File: multimodal.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Image inputs from multimodal chat content: data URL decoding,
+//     size limits and the image list forwarded to nodes.
+//
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ChatMessage, ContentPart};
+
+/// Placeholder marking where an image appears in the node prompt; images
+/// are forwarded in placeholder order.
+pub const IMAGE_PLACEHOLDER: &str = "<image>";
+
+/// Image forwarded to a node: inline bytes (base64) or a URL the node
+/// fetches itself.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum ImageInput {
+    Base64 {
+        media_type: String,
+        data: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    Url {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageLimits {
+    pub max_bytes: usize,
+    pub max_images: usize,
+}
+
+/// Room for the rest of a request body: axum's default body limit.
+const TEXT_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+impl ImageLimits {
+    /// Largest request body worth reading: `max_images` base64 data URLs
+    /// of `max_bytes` each, plus room for the text. Bigger bodies cannot
+    /// pass the image limits.
+    pub fn max_request_bytes(&self) -> usize {
+        let encoded = self.max_bytes.div_ceil(3).saturating_mul(4);
+        encoded
+            .saturating_mul(self.max_images)
+            .saturating_add(TEXT_BODY_BYTES)
+    }
+}
+
+/// Collects and validates every image in `messages`, in prompt order.
+pub fn collect_images(
+    messages: &[ChatMessage],
+    limits: ImageLimits,
+) -> Result<Vec<ImageInput>, String> {
+    let mut out = Vec::new();
+    for m in messages {
+        let Some(content) = &m.content else { continue };
+        for part in content.parts() {
+            let ContentPart::ImageUrl { image_url } = part else {
+                continue;
+            };
+            if out.len() == limits.max_images {
+                return Err(format!(
+                    "at most {} images are allowed per request",
+                    limits.max_images
+                ));
+            }
+            out.push(image_input(
+                &image_url.url,
+                image_url.detail,
+                limits.max_bytes,
+            )?);
+        }
+    }
+    Ok(out)
+}
+
+fn image_input(url: &str, detail: Option<String>, max_bytes: usize) -> Result<ImageInput, String> {
+    if let Some(rest) = url.strip_prefix("data:") {
+        let (media_type, data) = parse_data_url(rest)?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("image data URL is not valid base64: {}", e))?;
+        if bytes.len() > max_bytes {
+            return Err(format!(
+                "image is {} bytes; the limit is {}",
+                bytes.len(),
+                max_bytes
+            ));
+        }
+        return Ok(ImageInput::Base64 {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+            detail,
+        });
+    }
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return Ok(ImageInput::Url {
+            url: url.to_string(),
+            detail,
+        });
+    }
+    Err("image_url must be an http(s) URL or a base64 data URL".to_string())
+}
+
+/// Splits `<media type>;base64,<data>` (the part after `data:`).
+fn parse_data_url(rest: &str) -> Result<(&str, &str), String> {
+    let (meta, data) = rest
+        .split_once(',')
+        .ok_or_else(|| "malformed data URL".to_string())?;
+    let media_type = meta
+        .strip_suffix(";base64")
+        .ok_or_else(|| "image data URLs must be base64-encoded".to_string())?;
+    if !media_type.starts_with("image/") {
+        return Err(format!("unsupported image media type: {}", media_type));
+    }
+    Ok((media_type, data))
+}
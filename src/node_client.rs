@@ -2,9 +2,24 @@
 // Copyright (c) 2026 AURIA Developers and Contributors
 // Description:
 //     HTTP client for communicating with remote Auria Nodes.
-//     Handles request/response serialization for generation calls.
+//     Handles request/response serialization for generation calls
+//     and tracks node health and advertised capabilities.
 //
-use crate::models::Tier;
+use std::{
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, OnceLock, RwLock,
+    },
+    time::Duration,
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{
+    batch::{BatchConfig, Batcher},
+    models::{SamplingParams, Tier},
+    multimodal::ImageInput,
+};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -12,6 +27,78 @@ use url::Url;
 pub struct NodeClient {
     base: Url,
     http: reqwest::Client,
+    state: Arc<RwLock<NodeState>>,
+    batching: Option<BatchConfig>,
+    /// Started on the first generate call once the node advertises batching.
+    batcher: Arc<OnceLock<Batcher>>,
+    /// Request slots when the node has a concurrency limit.
+    slots: Option<Arc<Semaphore>>,
+}
+
+/// What a node advertises at `GET /v1/capabilities`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeCapabilities {
+    #[serde(default = "all_tiers")]
+    pub tiers: Vec<Tier>,
+    /// Whether the node serves `POST /v1/embeddings`. Opt-in: nodes that do
+    /// not advertise it never receive embedding requests.
+    #[serde(default)]
+    pub embeddings: bool,
+    /// Length of the node's embedding vectors, so `dimensions` can be
+    /// checked before a request is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_dimensions: Option<u32>,
+    /// Sampling parameters the node honours (see `SamplingParams::ALL`).
+    #[serde(default = "all_sampling_params")]
+    pub sampling: Vec<String>,
+    /// Whether the node can constrain decoding to a JSON Schema
+    /// (`guided_json` on generate requests).
+    #[serde(default)]
+    pub guided_decoding: bool,
+    /// Whether the node accepts `images` on generate requests.
+    #[serde(default)]
+    pub multimodal: bool,
+    /// Whether the node serves `POST /v1/generate/batch`.
+    #[serde(default)]
+    pub batch_generate: bool,
+    /// Whether the node serves `POST /v1/generate/cancel`.
+    #[serde(default)]
+    pub cancel: bool,
+    /// Chat template of the node's model, overriding the tier's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<String>,
+}
+
+impl Default for NodeCapabilities {
+    fn default() -> Self {
+        Self {
+            tiers: all_tiers(),
+            embeddings: false,
+            embedding_dimensions: None,
+            sampling: all_sampling_params(),
+            guided_decoding: false,
+            multimodal: false,
+            batch_generate: false,
+            cancel: false,
+            chat_template: None,
+        }
+    }
+}
+
+fn all_tiers() -> Vec<Tier> {
+    Tier::ALL.to_vec()
+}
+
+fn all_sampling_params() -> Vec<String> {
+    SamplingParams::ALL.iter().map(|s| s.to_string()).collect()
+}
+
+/// Last observed node state. Nodes start out healthy with default
+/// capabilities until the first check says otherwise.
+#[derive(Clone, Debug)]
+struct NodeState {
+    healthy: bool,
+    capabilities: NodeCapabilities,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -19,6 +106,18 @@ pub struct NodeGenerateRequest {
     pub tier: Tier,
     pub prompt: String,
     pub max_tokens: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    #[serde(flatten)]
+    pub sampling: SamplingParams,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guided_json: Option<serde_json::Value>,
+    /// Images referenced by `<image>` placeholders in `prompt`, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageInput>,
+    /// Agent-assigned id, used to cancel the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -27,14 +126,108 @@ pub struct NodeGenerateResponse {
     pub tokens_generated: u32,
 }
 
+/// Whether a generate request reached the node, so a caller that gives up
+/// knows if there is work to pay for. The request is either sent or
+/// abandoned, whichever happens first.
+#[derive(Clone, Debug, Default)]
+pub struct Delivery(Arc<AtomicU8>);
+
+impl Delivery {
+    const PENDING: u8 = 0;
+    const SENT: u8 = 1;
+    const ABANDONED: u8 = 2;
+
+    /// Marks the request as sent; false if the caller already gave up.
+    pub fn send(&self) -> bool {
+        self.settle(Self::SENT) == Self::SENT
+    }
+
+    /// Marks the request as abandoned; false if it was already sent.
+    pub fn abandon(&self) -> bool {
+        self.settle(Self::ABANDONED) == Self::ABANDONED
+    }
+
+    fn settle(&self, to: u8) -> u8 {
+        match self
+            .0
+            .compare_exchange(Self::PENDING, to, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => to,
+            Err(current) => current,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeCancelRequest {
+    pub request_id: String,
+}
+
+/// Tokens the node generated before it stopped.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeCancelResponse {
+    pub tokens_generated: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeEmbedRequest {
+    pub tier: Tier,
+    pub input: Vec<String>,
+}
+
+/// One full-dimension embedding per input, in input order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeEmbedResponse {
+    pub embeddings: Vec<Vec<f32>>,
+    pub prompt_tokens: u32,
+}
+
 impl NodeClient {
-    pub fn new(base: &str) -> anyhow::Result<Self> {
+    pub fn new(base: &str, timeout: Duration) -> anyhow::Result<Self> {
         Ok(Self {
             base: Url::parse(base)?,
-            http: reqwest::Client::new(),
+            http: reqwest::Client::builder().timeout(timeout).build()?,
+            state: Arc::new(RwLock::new(NodeState {
+                healthy: true,
+                capabilities: NodeCapabilities::default(),
+            })),
+            batching: None,
+            batcher: Arc::new(OnceLock::new()),
+            slots: None,
         })
     }
 
+    /// Caps generate requests in flight to this node at `limit`.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.slots = Some(Arc::new(Semaphore::new(limit)));
+        self
+    }
+
+    pub fn has_capacity(&self) -> bool {
+        self.slots
+            .as_ref()
+            .map(|s| s.available_permits() > 0)
+            .unwrap_or(true)
+    }
+
+    /// Waits for a request slot; `None` when the node is unlimited.
+    pub async fn acquire_slot(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.slots {
+            Some(s) => s.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+
+    /// Batches generate calls once the node advertises `batch_generate`.
+    pub fn with_batching(mut self, cfg: BatchConfig) -> Self {
+        self.batching = Some(cfg);
+        self
+    }
+
+    pub fn base(&self) -> &Url {
+        &self.base
+    }
+
     pub async fn healthz(&self) -> anyhow::Result<()> {
         let u = self.base.join("healthz")?;
         let r = self.http.get(u).send().await?;
@@ -44,7 +237,134 @@ impl NodeClient {
         Ok(())
     }
 
-    pub async fn generate(&self, req: NodeGenerateRequest) -> anyhow::Result<NodeGenerateResponse> {
+    pub fn is_healthy(&self) -> bool {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).healthy
+    }
+
+    pub fn capabilities(&self) -> NodeCapabilities {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .clone()
+    }
+
+    pub fn serves(&self, tier: Tier) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .tiers
+            .contains(&tier)
+    }
+
+    pub fn supports(&self, param: &str) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .sampling
+            .iter()
+            .any(|p| p == param)
+    }
+
+    pub fn supports_guided_decoding(&self) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .guided_decoding
+    }
+
+    pub fn is_multimodal(&self) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .multimodal
+    }
+
+    pub fn supports_batching(&self) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .batch_generate
+    }
+
+    pub fn supports_cancel(&self) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .cancel
+    }
+
+    pub fn embedding_dimensions(&self) -> Option<u32> {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .embedding_dimensions
+    }
+
+    pub fn chat_template(&self) -> Option<String> {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .capabilities
+            .chat_template
+            .clone()
+    }
+
+    pub fn serves_embeddings(&self, tier: Tier) -> bool {
+        let st = self.state.read().unwrap_or_else(|e| e.into_inner());
+        st.capabilities.embeddings && st.capabilities.tiers.contains(&tier)
+    }
+
+    /// Re-checks health and, if the node is up, its advertised capabilities.
+    /// Nodes without a capabilities endpoint keep the previous capabilities.
+    pub async fn refresh(&self) -> anyhow::Result<()> {
+        let health = self.healthz().await;
+        let caps = match health {
+            Ok(()) => self.fetch_capabilities().await.ok(),
+            Err(_) => None,
+        };
+
+        let mut st = self.state.write().unwrap_or_else(|e| e.into_inner());
+        st.healthy = health.is_ok();
+        if let Some(c) = caps {
+            st.capabilities = c;
+        }
+        health
+    }
+
+    async fn fetch_capabilities(&self) -> anyhow::Result<NodeCapabilities> {
+        let u = self.base.join("v1/capabilities")?;
+        let r = self.http.get(u).send().await?.error_for_status()?;
+        Ok(r.json().await?)
+    }
+
+    /// Generates on the node, marking `delivery` sent once the request
+    /// leaves the agent.
+    pub async fn generate(
+        &self,
+        req: NodeGenerateRequest,
+        delivery: &Delivery,
+    ) -> anyhow::Result<NodeGenerateResponse> {
+        if let Some(cfg) = self.batching.filter(|_| self.supports_batching()) {
+            let batcher = match self.batcher.get() {
+                Some(b) => b,
+                None => {
+                    let url = self.base.join("v1/generate/batch")?;
+                    self.batcher
+                        .get_or_init(|| Batcher::spawn(self.http.clone(), url, cfg))
+                }
+            };
+            return Ok(batcher.submit(req, delivery.clone()).await?);
+        }
+        delivery.send();
+
         // Production integration:
         // - Use the Auria Node API (AURIA Runtime Core) endpoint here.
         // - In the ARC skeleton, we didn't include an HTTP server; this agent is ready for that integration.
@@ -58,4 +378,42 @@ impl NodeClient {
             tokens_generated: 3,
         })
     }
+
+    /// Asks the node to stop generating `request_id`. `None` when the node
+    /// does not know the request: it never arrived or already finished.
+    pub async fn cancel(&self, request_id: &str) -> anyhow::Result<Option<NodeCancelResponse>> {
+        let u = self.base.join("v1/generate/cancel")?;
+        let r = self
+            .http
+            .post(u)
+            .json(&NodeCancelRequest {
+                request_id: request_id.to_string(),
+            })
+            .send()
+            .await?;
+        if r.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(r.error_for_status()?.json().await?))
+    }
+
+    pub async fn embed(&self, req: NodeEmbedRequest) -> anyhow::Result<NodeEmbedResponse> {
+        let u = self.base.join("v1/embeddings")?;
+        let r = self
+            .http
+            .post(u)
+            .json(&req)
+            .send()
+            .await?
+            .error_for_status()?;
+        let resp: NodeEmbedResponse = r.json().await?;
+        if resp.embeddings.len() != req.input.len() {
+            anyhow::bail!(
+                "node returned {} embeddings for {} inputs",
+                resp.embeddings.len(),
+                req.input.len()
+            );
+        }
+        Ok(resp)
+    }
 }
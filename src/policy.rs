@@ -2,10 +2,16 @@
 // Copyright (c) 2026 AURIA Developers and Contributors
 // Description:
 //     Policy engine for request tier enforcement and cost limiting.
-//     Validates tier requests and enforces maximum cost constraints.
+//     Validates tier requests, enforces maximum cost constraints and
+//     applies policy system prompts.
 //
-use crate::models::Tier;
+use crate::{
+    chat_template::TemplateMessage,
+    config::{PromptMode, SystemPromptSpec},
+    models::{SamplingParams, Tier},
+};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PolicyDecision {
@@ -15,6 +21,16 @@ pub struct PolicyDecision {
     pub deny_reason: Option<String>,
 }
 
+/// A policy system prompt applied to a request. Receipts carry the hash,
+/// not the text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedPrompt {
+    pub name: String,
+    pub mode: PromptMode,
+    /// Hex-encoded SHA3-256 of the prompt text.
+    pub sha3_256: String,
+}
+
 /// Minimal policy engine (production skeleton):
 /// - tier parsing
 /// - cost guard hooks
@@ -23,6 +39,7 @@ pub struct PolicyDecision {
 pub struct PolicyEngine {
     pub default_tier: Tier,
     pub max_cost_microusdc: u64,
+    pub system_prompts: Vec<SystemPromptSpec>,
 }
 
 impl PolicyEngine {
@@ -30,11 +47,7 @@ impl PolicyEngine {
         let tier = requested_tier.unwrap_or(self.default_tier);
         let max_tokens = max_tokens.unwrap_or(256).min(4096);
 
-        // v1 skeleton: max cost is not computed (needs fee registry + receipts).
-        if self.max_cost_microusdc > 0 {
-            // Hook point for: estimate_cost(tier, max_tokens) > max_cost
-        }
-
+        // Cost is checked by `check_cost` once prompt tokens are known.
         PolicyDecision {
             tier,
             max_tokens,
@@ -42,4 +55,174 @@ impl PolicyEngine {
             deny_reason: None,
         }
     }
+
+    /// Worst-case cost of `n` generations of up to `tokens` tokens each
+    /// (prompt plus `max_tokens`) at `price_per_1k` micro-USDC, rounded up.
+    pub fn estimate_cost(price_per_1k: u64, tokens: u32, n: u32) -> u64 {
+        (price_per_1k * tokens as u64 * n as u64).div_ceil(1000)
+    }
+
+    /// Rejects requests whose worst-case cost, on top of `spent` already
+    /// charged to the request, exceeds `max_cost_microusdc` (0 = unlimited);
+    /// returns the estimate otherwise.
+    pub fn check_cost(
+        &self,
+        spent: u64,
+        price_per_1k: u64,
+        tokens: u32,
+        n: u32,
+    ) -> Result<u64, String> {
+        let cost = Self::estimate_cost(price_per_1k, tokens, n);
+        if self.max_cost_microusdc > 0 && spent.saturating_add(cost) > self.max_cost_microusdc {
+            return Err(match spent {
+                0 => format!(
+                    "estimated cost {} micro-USDC for {} generation(s) exceeds the limit of {}",
+                    cost, n, self.max_cost_microusdc
+                ),
+                _ => format!(
+                    "estimated cost {} micro-USDC for {} more generation(s) exceeds the {} left of the limit of {}",
+                    cost,
+                    n,
+                    self.max_cost_microusdc.saturating_sub(spent),
+                    self.max_cost_microusdc
+                ),
+            });
+        }
+        Ok(cost)
+    }
+
+    /// Applies the system prompts matching `tenant` and `tier`, whatever the
+    /// client sent, as one leading system message: prepended prompts, then
+    /// the client's leading system message (or, if any prompt replaces it,
+    /// the replacing prompts and no client system message at all), then
+    /// appended prompts. Returns what was applied, in config order.
+    pub fn apply_system_prompts(
+        &self,
+        tenant: Option<&str>,
+        tier: Tier,
+        messages: &mut Vec<TemplateMessage>,
+    ) -> Vec<AppliedPrompt> {
+        let rules = self.matching_prompts(tenant, tier);
+        if rules.is_empty() {
+            return Vec::new();
+        }
+        let texts = |mode: PromptMode| {
+            rules
+                .iter()
+                .filter(move |r| r.mode == mode)
+                .map(|r| r.text.clone())
+        };
+
+        let mut system: Vec<String> = texts(PromptMode::Prepend).collect();
+        if rules.iter().any(|r| r.mode == PromptMode::Replace) {
+            messages.retain(|m| m.role != "system");
+            system.extend(texts(PromptMode::Replace));
+        } else if messages.first().is_some_and(|m| m.role == "system") {
+            system.push(messages.remove(0).content);
+        }
+        system.extend(texts(PromptMode::Append));
+        messages.insert(0, TemplateMessage::new("system", system.join("\n\n")));
+        applied(&rules)
+    }
+
+    /// Raw-prompt counterpart of [`apply_system_prompts`](Self::apply_system_prompts)
+    /// for legacy completions: prepended then appended prompts go in front
+    /// of `prompt`, as they would precede the conversation in chat. A raw
+    /// prompt has no client system message to replace, so a matching
+    /// `replace` prompt rejects the request instead.
+    pub fn apply_prompt_prefix(
+        &self,
+        tenant: Option<&str>,
+        tier: Tier,
+        prompt: &mut String,
+    ) -> Result<Vec<AppliedPrompt>, String> {
+        let rules = self.matching_prompts(tenant, tier);
+        if let Some(r) = rules.iter().find(|r| r.mode == PromptMode::Replace) {
+            return Err(format!(
+                "system prompt policy `{}` replaces client system prompts; use chat completions instead",
+                r.name
+            ));
+        }
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut parts: Vec<&str> = [PromptMode::Prepend, PromptMode::Append]
+            .iter()
+            .flat_map(|mode| {
+                rules
+                    .iter()
+                    .filter(move |r| r.mode == *mode)
+                    .map(|r| r.text.as_str())
+            })
+            .collect();
+        parts.push(prompt);
+        *prompt = parts.join("\n\n");
+        Ok(applied(&rules))
+    }
+
+    /// System prompts for `tenant` on `tier`, in config order.
+    fn matching_prompts(&self, tenant: Option<&str>, tier: Tier) -> Vec<&SystemPromptSpec> {
+        self.system_prompts
+            .iter()
+            .filter(|r| {
+                r.tenants.is_empty() || tenant.is_some_and(|t| r.tenants.iter().any(|n| n == t))
+            })
+            .filter(|r| r.tiers.is_empty() || r.tiers.contains(&tier))
+            .collect()
+    }
+
+    /// Checks sampling parameters against the OpenAI ranges.
+    pub fn validate_sampling(&self, p: &SamplingParams) -> Result<(), String> {
+        check_range("temperature", p.temperature, 0.0, 2.0)?;
+        check_range("top_p", p.top_p, 0.0, 1.0)?;
+        check_range("presence_penalty", p.presence_penalty, -2.0, 2.0)?;
+        check_range("frequency_penalty", p.frequency_penalty, -2.0, 2.0)?;
+
+        if let Some(stop) = &p.stop {
+            let seqs = stop.sequences();
+            if seqs.len() > MAX_STOP_SEQUENCES {
+                return Err(format!(
+                    "stop: at most {} sequences are allowed",
+                    MAX_STOP_SEQUENCES
+                ));
+            }
+            if seqs.iter().any(|s| s.is_empty()) {
+                return Err("stop: sequences must not be empty".to_string());
+            }
+        }
+        if let Some(bias) = &p.logit_bias {
+            for (token, b) in bias {
+                if token.parse::<u32>().is_err() {
+                    return Err(format!("logit_bias: key {:?} is not a token id", token));
+                }
+                check_range("logit_bias", Some(*b), -100.0, 100.0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Upper bound on `n` choices per request.
+pub const MAX_CHOICES: u32 = 16;
+
+fn check_range(name: &str, v: Option<f32>, min: f32, max: f32) -> Result<(), String> {
+    match v {
+        Some(x) if !(min..=max).contains(&x) => {
+            Err(format!("{}: {} is outside [{}, {}]", name, x, min, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn applied(rules: &[&SystemPromptSpec]) -> Vec<AppliedPrompt> {
+    rules
+        .iter()
+        .map(|r| AppliedPrompt {
+            name: r.name.clone(),
+            mode: r.mode,
+            sha3_256: hex::encode(Sha3_256::digest(r.text.as_bytes())),
+        })
+        .collect()
 }
@@ -0,0 +1,338 @@
+// File: receipts.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Usage receipts, the local append-only ledger and the settlement
+//     outbox shared by the HTTP server and the `auria receipts` CLI.
+//
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{models::Tier, policy::AppliedPrompt};
+
+const LEDGER_FILE: &str = "ledger.jsonl";
+const OUTBOX_FILE: &str = "outbox.jsonl";
+
+/// Usage record for a single request served by the agent.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UsageReceipt {
+    pub request_id: String,
+    pub created: i64,
+    pub model: String,
+    pub tier: Tier,
+    /// Base URL of the node that served the request.
+    pub node: String,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+    /// Set when served from the response cache: no node did the work, so
+    /// settlement does not credit `node`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached: Option<bool>,
+    /// Set when the request shared a concurrent identical request's node
+    /// call; like cached receipts, not credited to `node`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coalesced: Option<bool>,
+    /// Set when the client went away before the node finished; the tokens
+    /// are what the node produced until it stopped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancelled: Option<bool>,
+    /// Policy system prompts applied to the request, by hash.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_prompts: Vec<AppliedPrompt>,
+    /// Client charge in micro-USDC: tier price, or the cache-hit price for
+    /// cached responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charge_microusdc: Option<u64>,
+    /// Hex-encoded ed25519 public key of the signing agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// Hex-encoded ed25519 signature over `signing_payload()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    Valid,
+    Invalid,
+    Unsigned,
+    /// The signature matches the embedded `signer`, but that key is not
+    /// one of the trusted keys, so anyone could have minted the receipt.
+    Untrusted,
+}
+
+impl UsageReceipt {
+    /// Canonical bytes covered by the signature: the receipt serialized
+    /// as JSON with the signature field omitted.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature = None;
+        serde_json::to_vec(&unsigned).expect("receipt serializes")
+    }
+
+    /// Checks the signature and that `signer` is one of `trusted`
+    /// (hex-encoded ed25519 public keys, such as the keystore's active and
+    /// retired keys).
+    pub fn verify(&self, trusted: &[String]) -> anyhow::Result<VerifyOutcome> {
+        let (Some(signer), Some(signature)) = (&self.signer, &self.signature) else {
+            return Ok(VerifyOutcome::Unsigned);
+        };
+
+        let key_bytes: [u8; 32] = hex::decode(signer)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("signer key must be 32 bytes"))?;
+        let sig_bytes: [u8; 64] = hex::decode(signature)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("signature must be 64 bytes"))?;
+
+        let key = VerifyingKey::from_bytes(&key_bytes)?;
+        let sig = Signature::from_bytes(&sig_bytes);
+        Ok(match key.verify(&self.signing_payload(), &sig) {
+            Err(_) => VerifyOutcome::Invalid,
+            Ok(()) if !trusted.iter().any(|t| t.eq_ignore_ascii_case(signer)) => {
+                VerifyOutcome::Untrusted
+            }
+            Ok(()) => VerifyOutcome::Valid,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettlementStatus {
+    Pending,
+    Batched,
+    Submitted,
+    Settled,
+    Failed,
+}
+
+impl SettlementStatus {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pending" => Some(SettlementStatus::Pending),
+            "batched" => Some(SettlementStatus::Batched),
+            "submitted" => Some(SettlementStatus::Submitted),
+            "settled" => Some(SettlementStatus::Settled),
+            "failed" => Some(SettlementStatus::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettlementStatus::Pending => "pending",
+            SettlementStatus::Batched => "batched",
+            SettlementStatus::Submitted => "submitted",
+            SettlementStatus::Settled => "settled",
+            SettlementStatus::Failed => "failed",
+        }
+    }
+}
+
+/// One status transition of a receipt on its way to settlement.
+/// The outbox is append-only; the latest entry per request id wins.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub request_id: String,
+    pub status: SettlementStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<String>,
+    pub updated: i64,
+}
+
+/// Append-only JSONL ledger of receipts plus the settlement outbox,
+/// both stored under a single directory.
+#[derive(Clone, Debug)]
+pub struct Ledger {
+    dir: PathBuf,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl Ledger {
+    pub fn open(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            write_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Records a receipt and queues it in the outbox as pending.
+    pub fn append(&self, receipt: &UsageReceipt) -> anyhow::Result<()> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        append_line(&self.dir.join(LEDGER_FILE), receipt)?;
+        append_line(
+            &self.dir.join(OUTBOX_FILE),
+            &OutboxEntry {
+                request_id: receipt.request_id.clone(),
+                status: SettlementStatus::Pending,
+                batch: None,
+                updated: receipt.created,
+            },
+        )
+    }
+
+    pub fn update_status(&self, entry: &OutboxEntry) -> anyhow::Result<()> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        append_line(&self.dir.join(OUTBOX_FILE), entry)
+    }
+
+    /// Syncs the ledger and outbox to disk.
+    pub fn flush(&self) -> anyhow::Result<()> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        for name in [LEDGER_FILE, OUTBOX_FILE] {
+            match File::open(self.dir.join(name)) {
+                Ok(f) => f.sync_all()?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn receipts(&self) -> anyhow::Result<Vec<UsageReceipt>> {
+        read_lines(&self.dir.join(LEDGER_FILE))
+    }
+
+    pub fn find(&self, request_id: &str) -> anyhow::Result<Option<UsageReceipt>> {
+        Ok(self
+            .receipts()?
+            .into_iter()
+            .find(|r| r.request_id == request_id))
+    }
+
+    /// Receipts created in `[since, until)`; open bounds are unbounded.
+    pub fn range(
+        &self,
+        since: Option<i64>,
+        until: Option<i64>,
+    ) -> anyhow::Result<Vec<UsageReceipt>> {
+        Ok(self
+            .receipts()?
+            .into_iter()
+            .filter(|r| since.map(|s| r.created >= s).unwrap_or(true))
+            .filter(|r| until.map(|u| r.created < u).unwrap_or(true))
+            .collect())
+    }
+
+    pub fn outbox(&self) -> anyhow::Result<Vec<OutboxEntry>> {
+        read_lines(&self.dir.join(OUTBOX_FILE))
+    }
+
+    /// Current settlement status per request id.
+    pub fn settlement_status(&self) -> anyhow::Result<HashMap<String, OutboxEntry>> {
+        let mut latest = HashMap::new();
+        for e in self.outbox()? {
+            latest.insert(e.request_id.clone(), e);
+        }
+        Ok(latest)
+    }
+}
+
+pub fn now_unix() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+pub fn export_jsonl<W: Write>(mut out: W, receipts: &[UsageReceipt]) -> anyhow::Result<()> {
+    for r in receipts {
+        serde_json::to_writer(&mut out, r)?;
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+pub fn export_csv<W: Write>(out: W, receipts: &[UsageReceipt]) -> anyhow::Result<()> {
+    // Written field by field: the optional signature columns are skipped
+    // by serde for unsigned receipts, which csv would reject as ragged rows.
+    // Flags are `true` or empty; policy prompts are `name:sha3_256` joined
+    // by `;`.
+    let mut w = csv::Writer::from_writer(out);
+    w.write_record([
+        "request_id",
+        "created",
+        "model",
+        "tier",
+        "node",
+        "prompt_tokens",
+        "completion_tokens",
+        "total_tokens",
+        "cached",
+        "coalesced",
+        "cancelled",
+        "charge_microusdc",
+        "policy_prompts",
+        "signer",
+        "signature",
+    ])?;
+    let flag = |f: Option<bool>| f.map(|b| b.to_string()).unwrap_or_default();
+    for r in receipts {
+        let policy_prompts: Vec<String> = r
+            .policy_prompts
+            .iter()
+            .map(|p| format!("{}:{}", p.name, p.sha3_256))
+            .collect();
+        w.write_record([
+            r.request_id.clone(),
+            r.created.to_string(),
+            r.model.clone(),
+            r.tier.as_str().to_string(),
+            r.node.clone(),
+            r.prompt_tokens.to_string(),
+            r.completion_tokens.to_string(),
+            r.total_tokens.to_string(),
+            flag(r.cached),
+            flag(r.coalesced),
+            flag(r.cancelled),
+            r.charge_microusdc
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            policy_prompts.join(";"),
+            r.signer.clone().unwrap_or_default(),
+            r.signature.clone().unwrap_or_default(),
+        ])?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+fn append_line<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+    f.write_all(&line)?;
+    Ok(())
+}
+
+fn read_lines<T: for<'de> Deserialize<'de>>(path: &Path) -> anyhow::Result<Vec<T>> {
+    let f = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut out = Vec::new();
+    for (i, line) in BufReader::new(f).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let v = serde_json::from_str(&line)
+            .map_err(|e| anyhow::anyhow!("{}:{}: {}", path.display(), i + 1, e))?;
+        out.push(v);
+    }
+    Ok(out)
+}
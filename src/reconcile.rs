@@ -0,0 +1,151 @@
+// File: reconcile.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Reconciliation of node-reported token counts against locally
+//     counted tokens, with per-node discrepancy tracking and flagging.
+//
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use utoipa::ToSchema;
+
+/// Weight of the newest sample in the per-node reported/counted ratio.
+const EWMA_ALPHA: f64 = 0.1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconcileConfig {
+    /// Relative over-report tolerated before a response counts as a discrepancy.
+    pub tolerance: f64,
+    /// Samples required before a node can be flagged.
+    pub min_samples: u64,
+}
+
+/// Outcome of reconciling a single response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reconciliation {
+    pub reported: u32,
+    pub counted: u32,
+    /// Completion tokens to bill: the node's figure, unless it over-reported
+    /// beyond tolerance against an exact count, in which case the local count.
+    pub billed: u32,
+    pub over_reported: bool,
+}
+
+/// Running discrepancy statistics for one node.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct NodeTokenStats {
+    pub node: String,
+    pub samples: u64,
+    pub reported_tokens: u64,
+    pub counted_tokens: u64,
+    pub over_reports: u64,
+    /// Samples counted with the model's vocabulary; estimated counts are
+    /// tracked but never flag a node.
+    pub exact_samples: u64,
+    /// Exponentially weighted reported/counted ratio over exact samples.
+    pub ratio_ewma: f64,
+    /// Set while the node consistently over-reports; flagged nodes are
+    /// billed at the local count and deprioritised by routing.
+    pub flagged: bool,
+}
+
+pub struct Reconciler {
+    cfg: ReconcileConfig,
+    stats: Mutex<HashMap<String, NodeTokenStats>>,
+}
+
+impl Reconciler {
+    pub fn new(cfg: ReconcileConfig) -> Self {
+        Self {
+            cfg,
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reconciles against an exact local count, from a tokenizer file.
+    pub fn record(&self, node: &str, reported: u32, counted: u32) -> Reconciliation {
+        self.reconcile(node, reported, counted, true)
+    }
+
+    /// Records a response counted with an estimator. Estimates are too
+    /// rough to bill or flag on (code, CJK and whitespace-heavy text are
+    /// undercounted), so mismatches are only logged.
+    pub fn record_estimate(&self, node: &str, reported: u32, estimated: u32) -> Reconciliation {
+        self.reconcile(node, reported, estimated, false)
+    }
+
+    fn reconcile(&self, node: &str, reported: u32, counted: u32, exact: bool) -> Reconciliation {
+        let limit = counted as f64 * (1.0 + self.cfg.tolerance);
+        let over_reported = reported as f64 > limit.ceil();
+        let ratio = reported as f64 / counted.max(1) as f64;
+
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let s = stats
+            .entry(node.to_string())
+            .or_insert_with(|| NodeTokenStats {
+                node: node.to_string(),
+                ..Default::default()
+            });
+
+        s.samples += 1;
+        s.reported_tokens += reported as u64;
+        s.counted_tokens += counted as u64;
+        if over_reported {
+            s.over_reports += 1;
+        }
+        if !exact {
+            if over_reported {
+                debug!(
+                    "node {} reported {} tokens, estimated {}",
+                    node, reported, counted
+                );
+            }
+            return Reconciliation {
+                reported,
+                counted,
+                billed: reported,
+                over_reported,
+            };
+        }
+
+        s.ratio_ewma = match s.exact_samples {
+            0 => ratio,
+            _ => EWMA_ALPHA * ratio + (1.0 - EWMA_ALPHA) * s.ratio_ewma,
+        };
+        s.exact_samples += 1;
+
+        let should_flag =
+            s.exact_samples >= self.cfg.min_samples && s.ratio_ewma > 1.0 + self.cfg.tolerance;
+        if should_flag && !s.flagged {
+            warn!(
+                "node {} flagged: reports {:.2}x the locally counted tokens over {} samples",
+                node, s.ratio_ewma, s.exact_samples
+            );
+        }
+        s.flagged = should_flag;
+
+        Reconciliation {
+            reported,
+            counted,
+            billed: if over_reported || s.flagged {
+                reported.min(counted)
+            } else {
+                reported
+            },
+            over_reported,
+        }
+    }
+
+    pub fn is_flagged(&self, node: &str) -> bool {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.get(node).map(|s| s.flagged).unwrap_or(false)
+    }
+
+    pub fn report(&self) -> Vec<NodeTokenStats> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out: Vec<_> = stats.values().cloned().collect();
+        out.sort_by(|a, b| a.node.cmp(&b.node));
+        out
+    }
+}
@@ -34,6 +34,9 @@ impl NodePool {
     pub fn len(&self) -> usize {
         self.nodes.len()
     }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
     pub fn get(&self, idx: usize) -> &NodeClient {
         &self.nodes[idx % self.nodes.len()]
     }
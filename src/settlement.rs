@@ -0,0 +1,193 @@
+// File: settlement.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Settlement batches built from usage receipts and their EIP-712
+//     encoding for the Base settlement contracts (offline signing only).
+//
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    eip712::{self, keccak256, EthSignature, TypedData, TypedField},
+    receipts::UsageReceipt,
+};
+
+/// Per-node totals inside a settlement batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeTotal {
+    /// `keccak256(node base URL)`, hex-encoded.
+    pub node: String,
+    pub receipts: u64,
+    pub tokens: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettlementBatch {
+    pub epoch: u64,
+    /// Merkle root over receipt leaves, hex-encoded.
+    pub root: String,
+    pub total_tokens: u64,
+    pub nodes: Vec<NodeTotal>,
+}
+
+/// EIP-712 domain of the settlement contract.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettlementDomain {
+    pub chain_id: u64,
+    pub verifying_contract: String,
+}
+
+/// A batch together with its typed data, digest and signature, ready to
+/// be submitted to the settlement contract.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedBatch {
+    pub batch: SettlementBatch,
+    pub typed_data: TypedData,
+    pub digest: String,
+    pub signer: String,
+    pub signature: EthSignature,
+    pub signature_hex: String,
+}
+
+impl SettlementBatch {
+    /// Aggregates `receipts` into a batch. Leaves are sorted so the root
+    /// does not depend on ledger order. Cached and coalesced receipts are
+    /// committed to but not credited to any node.
+    pub fn from_receipts(epoch: u64, receipts: &[UsageReceipt]) -> Self {
+        let mut per_node: BTreeMap<String, NodeTotal> = BTreeMap::new();
+        let mut total_tokens = 0u64;
+        let mut leaves: Vec<[u8; 32]> = Vec::with_capacity(receipts.len());
+
+        for r in receipts {
+            leaves.push(receipt_leaf(r));
+            if r.cached == Some(true) || r.coalesced == Some(true) {
+                continue;
+            }
+            let node = format!("0x{}", hex::encode(keccak256(r.node.as_bytes())));
+            let t = per_node.entry(node.clone()).or_insert(NodeTotal {
+                node,
+                receipts: 0,
+                tokens: 0,
+            });
+            t.receipts += 1;
+            t.tokens += r.total_tokens as u64;
+            total_tokens += r.total_tokens as u64;
+        }
+        leaves.sort();
+
+        SettlementBatch {
+            epoch,
+            root: format!("0x{}", hex::encode(merkle_root(&leaves))),
+            total_tokens,
+            nodes: per_node.into_values().collect(),
+        }
+    }
+
+    pub fn typed_data(&self, domain: &SettlementDomain) -> TypedData {
+        let field = |name: &str, ty: &str| TypedField {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        };
+        let mut types = BTreeMap::new();
+        types.insert(
+            "EIP712Domain".to_string(),
+            vec![
+                field("name", "string"),
+                field("version", "string"),
+                field("chainId", "uint256"),
+                field("verifyingContract", "address"),
+            ],
+        );
+        types.insert(
+            "SettlementBatch".to_string(),
+            vec![
+                field("epoch", "uint64"),
+                field("root", "bytes32"),
+                field("totalTokens", "uint256"),
+                field("nodes", "NodeTotal[]"),
+            ],
+        );
+        types.insert(
+            "NodeTotal".to_string(),
+            vec![
+                field("node", "bytes32"),
+                field("receipts", "uint64"),
+                field("tokens", "uint256"),
+            ],
+        );
+
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|n| json!({ "node": n.node, "receipts": n.receipts, "tokens": n.tokens }))
+            .collect();
+
+        TypedData {
+            types,
+            primary_type: "SettlementBatch".to_string(),
+            domain: json!({
+                "name": "AuriaSettlement",
+                "version": "1",
+                "chainId": domain.chain_id,
+                "verifyingContract": domain.verifying_contract,
+            }),
+            message: json!({
+                "epoch": self.epoch,
+                "root": self.root,
+                "totalTokens": self.total_tokens,
+                "nodes": nodes,
+            }),
+        }
+    }
+
+    pub fn sign(
+        self,
+        domain: &SettlementDomain,
+        key: &k256::ecdsa::SigningKey,
+    ) -> anyhow::Result<SignedBatch> {
+        let typed_data = self.typed_data(domain);
+        let digest = format!("0x{}", hex::encode(typed_data.signing_hash()?));
+        let signature = typed_data.sign(key)?;
+        Ok(SignedBatch {
+            signer: eip712::address_of(key.verifying_key()),
+            signature_hex: signature.to_hex(),
+            batch: self,
+            typed_data,
+            digest,
+            signature,
+        })
+    }
+}
+
+/// Leaf committed for a receipt: `keccak256(signing_payload)`.
+pub fn receipt_leaf(r: &UsageReceipt) -> [u8; 32] {
+    keccak256(&r.signing_payload())
+}
+
+/// Merkle root with sorted-pair hashing (compatible with OpenZeppelin's
+/// `MerkleProof`). An odd node is promoted unchanged; empty input yields zero.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => {
+                    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+                    let mut buf = [0u8; 64];
+                    buf[..32].copy_from_slice(lo);
+                    buf[32..].copy_from_slice(hi);
+                    keccak256(&buf)
+                }
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
@@ -0,0 +1,113 @@
+// File: structured.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Structured outputs for `response_format`: prompt instructions,
+//     validation of node output against JSON Schema and local repair
+//     of near-miss JSON.
+//
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+use crate::models::ResponseFormat;
+
+/// Compiled `response_format` constraint.
+pub struct OutputSchema {
+    name: String,
+    schema: Option<Value>,
+    compiled: Option<JSONSchema>,
+}
+
+impl OutputSchema {
+    /// `None` for `text`; errors if the supplied schema does not compile.
+    pub fn from_format(format: &ResponseFormat) -> Result<Option<Self>, String> {
+        match format {
+            ResponseFormat::Text => Ok(None),
+            ResponseFormat::JsonObject => Ok(Some(Self {
+                name: "json_object".to_string(),
+                schema: None,
+                compiled: None,
+            })),
+            ResponseFormat::JsonSchema { json_schema } => {
+                let compiled = match &json_schema.schema {
+                    Some(s) => Some(JSONSchema::compile(s).map_err(|e| {
+                        format!("response_format.json_schema.schema is invalid: {}", e)
+                    })?),
+                    None => None,
+                };
+                Ok(Some(Self {
+                    name: json_schema.name.clone(),
+                    schema: json_schema.schema.clone(),
+                    compiled,
+                }))
+            }
+        }
+    }
+
+    /// Schema to forward as a guided-decoding hint, if any.
+    pub fn schema(&self) -> Option<&Value> {
+        self.schema.as_ref()
+    }
+
+    /// System prompt section asking for conforming JSON.
+    pub fn instructions(&self) -> String {
+        match &self.schema {
+            Some(s) => format!(
+                "Respond only with a JSON value named {} that conforms to this JSON Schema, without any other text:\n{}\n",
+                self.name, s
+            ),
+            None => "Respond only with a single JSON object, without any other text.\n".to_string(),
+        }
+    }
+
+    /// Parses `text` (repairing it locally if needed) and validates it.
+    /// Returns the canonical JSON text on success.
+    pub fn check(&self, text: &str) -> Result<String, String> {
+        let value = repair(text).ok_or_else(|| "output is not valid JSON".to_string())?;
+        match &self.compiled {
+            Some(schema) => {
+                if let Err(errors) = schema.validate(&value) {
+                    let msgs: Vec<String> = errors
+                        .map(|e| format!("{} at '{}'", e, e.instance_path))
+                        .collect();
+                    return Err(format!(
+                        "output does not match schema {}: {}",
+                        self.name,
+                        msgs.join("; ")
+                    ));
+                }
+            }
+            None if !value.is_object() => return Err("output is not a JSON object".to_string()),
+            None => {}
+        }
+        Ok(value.to_string())
+    }
+}
+
+/// Recovers JSON from common near misses: surrounding prose, Markdown
+/// code fences and trailing text after the closing brace.
+pub fn repair(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if let Ok(v) = serde_json::from_str(trimmed) {
+        return Some(v);
+    }
+
+    let unfenced = trimmed
+        .split("```")
+        .nth(1)
+        .map(|block| block.trim_start_matches("json").trim())
+        .unwrap_or(trimmed);
+    if let Ok(v) = serde_json::from_str(unfenced) {
+        return Some(v);
+    }
+
+    for (open, close) in [('{', '}'), ('[', ']')] {
+        if let (Some(start), Some(end)) = (unfenced.find(open), unfenced.rfind(close)) {
+            if start < end {
+                if let Ok(v) = serde_json::from_str(&unfenced[start..=end]) {
+                    return Some(v);
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,192 @@
+// File: tokenizer.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tokenizer abstraction used to count prompt and completion tokens
+//     locally, with built-in estimators and file-backed BPE tokenizers
+//     selected per tier.
+//
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Arc,
+};
+
+use base64::Engine;
+
+use crate::models::Tier;
+
+/// Pre-tokenization pattern of the cl100k_base encoding, used for
+/// tokenizer files unless `tokenizer_pattern` is configured.
+pub const CL100K_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+(?!\S)|\s+";
+
+/// Counts tokens in text. Implementations must be deterministic.
+pub trait Tokenizer: Send + Sync {
+    fn name(&self) -> &str;
+    fn count(&self, text: &str) -> u32;
+
+    /// Whether counts come from the model's vocabulary rather than an
+    /// estimate; only exact counts can overrule what a node reports.
+    fn is_exact(&self) -> bool {
+        false
+    }
+}
+
+/// Rule-of-thumb estimate for BPE vocabularies: one token per four
+/// characters, rounded up.
+pub struct ApproxTokenizer;
+
+impl Tokenizer for ApproxTokenizer {
+    fn name(&self) -> &str {
+        "approx"
+    }
+
+    fn count(&self, text: &str) -> u32 {
+        let chars = text.chars().count() as u32;
+        chars.div_ceil(4)
+    }
+}
+
+/// Counts words and punctuation marks as separate tokens.
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn name(&self) -> &str {
+        "whitespace"
+    }
+
+    fn count(&self, text: &str) -> u32 {
+        let mut n = 0;
+        let mut in_word = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                in_word = false;
+            } else if c.is_alphanumeric() {
+                if !in_word {
+                    n += 1;
+                    in_word = true;
+                }
+            } else {
+                n += 1;
+                in_word = false;
+            }
+        }
+        n
+    }
+}
+
+/// Byte-level BPE tokenizer loaded from a tiktoken rank file
+/// (`<base64 token> <rank>` per line).
+pub struct BpeTokenizer {
+    name: String,
+    ranks: HashMap<Vec<u8>, u32>,
+    pattern: fancy_regex::Regex,
+}
+
+impl BpeTokenizer {
+    pub fn from_file(path: impl AsRef<Path>, pattern: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut ranks = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (token, rank) = line.split_once(' ').ok_or_else(|| {
+                anyhow::anyhow!("{}:{}: expected `<token> <rank>`", path.display(), i + 1)
+            })?;
+            let token = base64::engine::general_purpose::STANDARD.decode(token)?;
+            ranks.insert(token, rank.trim().parse()?);
+        }
+        Ok(Self {
+            name: path.display().to_string(),
+            ranks,
+            pattern: fancy_regex::Regex::new(pattern)?,
+        })
+    }
+
+    /// Number of tokens `piece` encodes to, merging the lowest-ranked
+    /// adjacent pair until no known pair remains.
+    fn piece_tokens(&self, piece: &[u8]) -> u32 {
+        if self.ranks.contains_key(piece) {
+            return 1;
+        }
+        let mut bounds: Vec<usize> = (0..=piece.len()).collect();
+        loop {
+            let mut best: Option<(u32, usize)> = None;
+            for i in 0..bounds.len().saturating_sub(2) {
+                if let Some(&rank) = self.ranks.get(&piece[bounds[i]..bounds[i + 2]]) {
+                    if best.map(|(r, _)| rank < r).unwrap_or(true) {
+                        best = Some((rank, i));
+                    }
+                }
+            }
+            match best {
+                Some((_, i)) => {
+                    bounds.remove(i + 1);
+                }
+                None => break,
+            }
+        }
+        (bounds.len() - 1) as u32
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count(&self, text: &str) -> u32 {
+        self.pattern
+            .find_iter(text)
+            .filter_map(Result::ok)
+            .map(|m| self.piece_tokens(m.as_str().as_bytes()))
+            .sum()
+    }
+
+    fn is_exact(&self) -> bool {
+        true
+    }
+}
+
+/// Tokenizers per tier, falling back to a default for unmapped tiers.
+#[derive(Clone)]
+pub struct TokenizerRegistry {
+    default: Arc<dyn Tokenizer>,
+    per_tier: HashMap<Tier, Arc<dyn Tokenizer>>,
+}
+
+impl TokenizerRegistry {
+    /// `default` names a built-in tokenizer; `files` maps tiers to tiktoken
+    /// rank files, all sharing `pattern` (cl100k when unset).
+    pub fn load(
+        default: &str,
+        files: &BTreeMap<Tier, String>,
+        pattern: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let pattern = pattern.unwrap_or(CL100K_PATTERN);
+        let mut per_tier: HashMap<Tier, Arc<dyn Tokenizer>> = HashMap::new();
+        for (tier, path) in files {
+            let tok = BpeTokenizer::from_file(path, pattern)
+                .map_err(|e| anyhow::anyhow!("loading tokenizer for {}: {}", tier.as_str(), e))?;
+            per_tier.insert(*tier, Arc::new(tok));
+        }
+        Ok(Self {
+            default: builtin(default)?,
+            per_tier,
+        })
+    }
+
+    pub fn for_tier(&self, tier: Tier) -> &dyn Tokenizer {
+        self.per_tier.get(&tier).unwrap_or(&self.default).as_ref()
+    }
+}
+
+/// Resolves a built-in tokenizer by name.
+pub fn builtin(name: &str) -> anyhow::Result<Arc<dyn Tokenizer>> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "approx" => Ok(Arc::new(ApproxTokenizer)),
+        "whitespace" => Ok(Arc::new(WhitespaceTokenizer)),
+        other => anyhow::bail!("unknown tokenizer: {}", other),
+    }
+}
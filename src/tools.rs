@@ -0,0 +1,166 @@
+// File: tools.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     OpenAI tool calling on top of plain-text nodes: tool definitions
+//     are rendered into the prompt and tool calls are parsed back out of
+//     the generated text.
+//
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::models::{FunctionCall, Tool, ToolCall, ToolChoice};
+
+pub const CALL_OPEN: &str = "<tool_call>";
+pub const CALL_CLOSE: &str = "</tool_call>";
+
+/// How a request wants tools used, after validating `tool_choice`
+/// against the declared tools.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ToolMode {
+    /// No tools declared, or `tool_choice: "none"`.
+    Disabled,
+    Auto,
+    Required,
+    Function(String),
+}
+
+impl ToolMode {
+    pub fn resolve(tools: &[Tool], choice: Option<&ToolChoice>) -> Result<Self, String> {
+        if let Some(t) = tools.iter().find(|t| t.kind != "function") {
+            return Err(format!("unsupported tool type: {}", t.kind));
+        }
+        let mode = match choice {
+            None if tools.is_empty() => Self::Disabled,
+            None => Self::Auto,
+            Some(ToolChoice::Mode(m)) => match m.as_str() {
+                "none" => Self::Disabled,
+                "auto" => Self::Auto,
+                "required" => Self::Required,
+                other => return Err(format!("invalid tool_choice: {}", other)),
+            },
+            Some(ToolChoice::Function { function, .. }) => {
+                if !tools.iter().any(|t| t.function.name == function.name) {
+                    return Err(format!(
+                        "tool_choice names unknown function: {}",
+                        function.name
+                    ));
+                }
+                Self::Function(function.name.clone())
+            }
+        };
+        if mode != Self::Disabled && tools.is_empty() {
+            return Err("tool_choice requires tools".to_string());
+        }
+        Ok(mode)
+    }
+}
+
+/// Checks the calls parsed from a choice against the mode: `required`
+/// needs at least one call, and a named function exactly that function.
+pub fn check_calls(mode: &ToolMode, calls: &[ToolCall]) -> Result<(), String> {
+    match mode {
+        ToolMode::Required if calls.is_empty() => {
+            Err("tool_choice is \"required\" but no tool was called".to_string())
+        }
+        ToolMode::Function(name) => match calls.iter().find(|c| c.function.name != *name) {
+            Some(c) => Err(format!(
+                "tool_choice names {} but {} was called",
+                name, c.function.name
+            )),
+            None if calls.is_empty() => {
+                Err(format!("tool_choice names {} but no tool was called", name))
+            }
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// System prompt section describing the tools and the call syntax.
+pub fn render_tools(tools: &[Tool], mode: &ToolMode) -> String {
+    let mut out = String::from("You can call the following tools:\n");
+    for t in tools {
+        let f = &t.function;
+        out.push_str(&format!("- {}", f.name));
+        if let Some(d) = &f.description {
+            out.push_str(&format!(": {}", d));
+        }
+        if let Some(p) = &f.parameters {
+            out.push_str(&format!("\n  parameters: {}", p));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "To call a tool, reply with {}{{\"name\": <tool name>, \"arguments\": <arguments object>}}{} \
+         (one block per call).\n",
+        CALL_OPEN, CALL_CLOSE
+    ));
+    match mode {
+        ToolMode::Required => out.push_str("You must call at least one tool.\n"),
+        ToolMode::Function(name) => out.push_str(&format!("You must call the tool {}.\n", name)),
+        ToolMode::Auto | ToolMode::Disabled => {}
+    }
+    out
+}
+
+/// Renders a call the way nodes are asked to emit it, so earlier
+/// assistant turns in the conversation match the expected syntax.
+pub fn render_call(call: &ToolCall) -> String {
+    let args: Value = serde_json::from_str(&call.function.arguments)
+        .unwrap_or_else(|_| Value::String(call.function.arguments.clone()));
+    let body = serde_json::json!({ "name": call.function.name, "arguments": args });
+    format!("{}{}{}", CALL_OPEN, body, CALL_CLOSE)
+}
+
+/// Splits generated text into remaining content and tool calls. Blocks
+/// that are not valid calls to a declared tool are left in the content.
+pub fn parse_tool_calls(text: &str, tools: &[Tool]) -> (Option<String>, Vec<ToolCall>) {
+    let mut content = String::new();
+    let mut calls = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(CALL_OPEN) {
+        let after = &rest[start + CALL_OPEN.len()..];
+        let Some(end) = after.find(CALL_CLOSE) else {
+            break;
+        };
+        content.push_str(&rest[..start]);
+        match parse_call(after[..end].trim(), tools) {
+            Some(call) => calls.push(call),
+            None => {
+                content.push_str(&rest[start..start + CALL_OPEN.len() + end + CALL_CLOSE.len()])
+            }
+        }
+        rest = &after[end + CALL_CLOSE.len()..];
+    }
+    content.push_str(rest);
+
+    let content = content.trim();
+    let content = if content.is_empty() && !calls.is_empty() {
+        None
+    } else {
+        Some(content.to_string())
+    };
+    (content, calls)
+}
+
+fn parse_call(body: &str, tools: &[Tool]) -> Option<ToolCall> {
+    let v: Value = serde_json::from_str(body).ok()?;
+    let name = v.get("name")?.as_str()?;
+    if !tools.iter().any(|t| t.function.name == name) {
+        return None;
+    }
+    let arguments = match v.get("arguments") {
+        None | Some(Value::Null) => "{}".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    Some(ToolCall {
+        id: format!("call_{}", Uuid::new_v4().simple()),
+        kind: "function".to_string(),
+        function: FunctionCall {
+            name: name.to_string(),
+            arguments,
+        },
+    })
+}
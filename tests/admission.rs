@@ -0,0 +1,140 @@
+// File: admission.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for admission control: priority ordering, the bounded queue,
+//     wait deadlines and tenant priorities.
+//
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use auria::{
+    admission::{Admission, AdmissionConfig},
+    config::{AppConfig, TenantSpec},
+    error::AgentError,
+    models::Tier,
+    AuriaAgent,
+};
+use axum::{http::header, response::IntoResponse};
+
+fn admission(queue_size: usize, max_wait: Duration) -> Arc<Admission> {
+    Arc::new(Admission::new(AdmissionConfig {
+        tier_limits: BTreeMap::from([(Tier::Nano, 1)]),
+        queue_size,
+        max_wait,
+    }))
+}
+
+#[tokio::test]
+async fn admits_by_priority_within_a_bounded_queue() {
+    let adm = admission(2, Duration::from_secs(5));
+    let held = adm.admit(Tier::Nano, 0).await.unwrap();
+    // Unlimited tiers are never queued.
+    let _pro = adm.admit(Tier::Pro, 0).await.unwrap();
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    for (name, priority) in [("low", 0), ("high", 10)] {
+        let (adm, tx) = (adm.clone(), tx.clone());
+        tokio::spawn(async move {
+            let _permit = adm.admit(Tier::Nano, priority).await.unwrap();
+            tx.send(name).unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(adm.queued(Tier::Nano), 2);
+    let Err(full) = adm.admit(Tier::Nano, 100).await else {
+        panic!("queue should be full")
+    };
+    assert!(matches!(full, AgentError::RateLimit(..)));
+
+    drop(held);
+    assert_eq!(rx.recv().await, Some("high"));
+    assert_eq!(rx.recv().await, Some("low"));
+}
+
+#[tokio::test]
+async fn retry_after_follows_the_wait_estimate() {
+    let adm = admission(2, Duration::from_secs(2));
+    // Slots are held ~1.2s on average.
+    let held = adm.admit(Tier::Nano, 0).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    drop(held);
+    let _held = adm.admit(Tier::Nano, 0).await.unwrap();
+
+    let wait = |priority: i32| {
+        let adm = adm.clone();
+        tokio::spawn(async move {
+            let _ = adm.admit(Tier::Nano, priority).await;
+        })
+    };
+    let low = wait(0);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // Two holds ahead, ~2.4s: past the deadline.
+    let Err(saturated) = adm.admit(Tier::Nano, 0).await else {
+        panic!("wait should exceed the deadline")
+    };
+    assert!(matches!(saturated, AgentError::NoCapacity(..)));
+    assert_eq!(saturated.retry_after(), Some(3));
+
+    // A high-priority request jumps the queue and fills it; three holds
+    // ahead of the next one, ~3.6s.
+    let high = wait(10);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let Err(full) = adm.admit(Tier::Nano, 0).await else {
+        panic!("queue should be full")
+    };
+    assert!(matches!(full, AgentError::RateLimit(..)));
+    let resp = full.with_request_id("req_1").into_response();
+    assert_eq!(resp.status(), 429);
+    assert_eq!(resp.headers()[header::RETRY_AFTER], "4");
+
+    low.abort();
+    high.abort();
+}
+
+#[tokio::test]
+async fn fails_fast_past_the_deadline() {
+    let adm = admission(8, Duration::from_millis(50));
+    let held = adm.admit(Tier::Nano, 0).await.unwrap();
+    let Err(err) = adm.admit(Tier::Nano, 0).await else {
+        panic!("slot should be taken")
+    };
+    assert!(matches!(err, AgentError::NoCapacity(..)));
+    let resp = err.with_request_id("req_1").into_response();
+    assert_eq!(resp.status(), 503);
+    assert_eq!(resp.headers()[header::RETRY_AFTER], "1");
+
+    // Slots are held ~200ms on average, so a 50ms deadline is hopeless.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    drop(held);
+    let _held = adm.admit(Tier::Nano, 0).await.unwrap();
+    let start = Instant::now();
+    assert!(matches!(
+        adm.admit(Tier::Nano, 0).await,
+        Err(AgentError::NoCapacity(..))
+    ));
+    assert!(start.elapsed() < Duration::from_millis(40));
+
+    let cfg = AppConfig {
+        tenants: BTreeMap::from([(
+            "acme".to_string(),
+            TenantSpec {
+                api_keys: vec!["sk-acme".to_string()],
+                priority: 10,
+            },
+        )]),
+        request_classes: BTreeMap::from([("batch".to_string(), -5)]),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    let caller = agent.caller(Some("sk-acme"), Some("batch"));
+    assert_eq!(
+        (caller.tenant.as_deref(), caller.priority),
+        (Some("acme"), 5)
+    );
+    assert_eq!(agent.caller(Some("sk-other"), None).priority, 0);
+}
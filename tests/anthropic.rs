@@ -0,0 +1,109 @@
+// File: anthropic.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for the Anthropic Messages front-end: request translation,
+//     responses and streaming events through the shared chat path.
+//
+use auria::{
+    anthropic::{self, ContentBlock, MessagesRequest},
+    config::AppConfig,
+    models::MessageContent,
+    AuriaAgent,
+};
+use serde_json::json;
+
+fn request(stream: bool) -> MessagesRequest {
+    serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "max_tokens": 64,
+        "system": "Be brief.",
+        "stream": stream,
+        "messages": [
+            { "role": "user", "content": "Weather?" },
+            { "role": "assistant", "content": [
+                { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "Oslo" } },
+            ]},
+            { "role": "user", "content": [
+                { "type": "tool_result", "tool_use_id": "toolu_1", "content": "cloudy" },
+                { "type": "text", "text": "Thanks" },
+            ]},
+        ],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn translates_to_chat_messages() {
+    let chat = anthropic::to_chat_request(request(false)).unwrap();
+    let roles: Vec<_> = chat.messages.iter().map(|m| m.role.as_str()).collect();
+    assert_eq!(roles, vec!["system", "user", "assistant", "tool", "user"]);
+    assert_eq!(
+        chat.messages[0].content,
+        Some(MessageContent::Text("Be brief.".to_string()))
+    );
+    assert_eq!(
+        chat.messages[2].tool_calls.as_ref().unwrap()[0]
+            .function
+            .arguments,
+        r#"{"city":"Oslo"}"#
+    );
+    assert_eq!(chat.messages[3].tool_call_id.as_deref(), Some("toolu_1"));
+    assert_eq!(chat.max_tokens, Some(64));
+
+    let mut top_k = request(false);
+    top_k.top_k = Some(5);
+    assert!(anthropic::to_chat_request(top_k).is_err());
+}
+
+#[tokio::test]
+async fn shares_the_chat_path() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+
+    let resp = agent
+        .chat_completions(anthropic::to_chat_request(request(false)).unwrap())
+        .await
+        .unwrap();
+    let msg = anthropic::from_chat_response(resp.clone());
+    assert_eq!(msg.stop_reason.as_deref(), Some("end_turn"));
+    assert!(matches!(&msg.content[0], ContentBlock::Text { .. }));
+    assert_eq!(msg.usage.input_tokens, resp.usage.prompt_tokens);
+
+    let chunks = agent
+        .chat_completion_chunks(anthropic::to_chat_request(request(true)).unwrap())
+        .await
+        .unwrap();
+    let events: Vec<_> = anthropic::stream_events(&chunks)
+        .into_iter()
+        .map(|e| e.event)
+        .collect();
+    assert_eq!(events.first(), Some(&"message_start"));
+    assert_eq!(events[1], "content_block_start");
+    assert!(events.contains(&"content_block_delta"));
+    assert_eq!(
+        &events[events.len() - 3..],
+        ["content_block_stop", "message_delta", "message_stop"]
+    );
+}
+
+#[tokio::test]
+async fn reports_max_tokens_when_the_budget_runs_out() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    // The stub node echoes the prompt, well past a two-token budget.
+    let mut req = request(false);
+    req.max_tokens = 2;
+
+    let resp = agent
+        .chat_completions(anthropic::to_chat_request(req).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.choices[0].finish_reason, "length");
+    assert!(resp.usage.completion_tokens >= 2);
+    let msg = anthropic::from_chat_response(resp);
+    assert_eq!(msg.stop_reason.as_deref(), Some("max_tokens"));
+
+    let completion =
+        serde_json::from_value(json!({ "model": "AURIA:NANO", "prompt": "hi", "max_tokens": 2 }))
+            .unwrap();
+    let resp = agent.completions(completion).await.unwrap();
+    assert_eq!(resp.choices[0].finish_reason, "length");
+}
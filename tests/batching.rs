@@ -0,0 +1,103 @@
+// File: batching.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for per-node micro-batching against a mock node serving
+//     batch generate calls.
+//
+mod common;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use auria::{config::AppConfig, error::AgentError, models::CompletionRequest, AuriaAgent};
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+/// Echoes each prompt; prompts containing "fail" get a per-item error.
+async fn mock_node(batches: Arc<AtomicUsize>) -> String {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "batch_generate": true })) }),
+        )
+        .route(
+            "/v1/generate/batch",
+            post(move |Json(batch): Json<Value>| async move {
+                batches.fetch_add(1, Ordering::SeqCst);
+                let results: Vec<Value> = batch["requests"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|r| match r["prompt"].as_str().unwrap() {
+                        p if p.contains("fail") => json!({ "error": "prompt rejected" }),
+                        p => json!({ "tokens": [p], "tokens_generated": 1 }),
+                    })
+                    .collect();
+                Json(json!({ "results": results }))
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+async fn agent(window_ms: u64, max_batch_size: usize, batches: Arc<AtomicUsize>) -> AuriaAgent {
+    let cfg = AppConfig {
+        node_urls: vec![mock_node(batches).await],
+        batch_window_ms: window_ms,
+        max_batch_size,
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    agent
+}
+
+fn completion(model: &str, prompt: &str) -> CompletionRequest {
+    serde_json::from_value(json!({ "model": model, "prompt": prompt })).unwrap()
+}
+
+#[tokio::test]
+async fn batches_requests_and_demultiplexes_results() {
+    let batches = Arc::new(AtomicUsize::new(0));
+    let agent = agent(50, 8, batches.clone()).await;
+
+    let prompts = ["one", "two", "fail three"];
+    let results = futures::future::join_all(
+        prompts
+            .iter()
+            .map(|p| agent.completions(completion("AURIA:NANO", p))),
+    )
+    .await;
+    assert_eq!(batches.load(Ordering::SeqCst), 1);
+    assert_eq!(results[0].as_ref().unwrap().choices[0].text, "one");
+    assert_eq!(results[1].as_ref().unwrap().choices[0].text, "two");
+    assert!(
+        matches!(&results[2], Err(AgentError::UpstreamError(m)) if m.contains("prompt rejected"))
+    );
+}
+
+#[tokio::test]
+async fn splits_batches_by_size_and_tier() {
+    let batches = Arc::new(AtomicUsize::new(0));
+    let agent = agent(50, 2, batches.clone()).await;
+
+    let reqs = [
+        ("AURIA:NANO", "a"),
+        ("AURIA:NANO", "b"),
+        ("AURIA:NANO", "c"),
+        ("AURIA:PRO", "d"),
+    ];
+    let results = futures::future::join_all(
+        reqs.iter()
+            .map(|(m, p)| agent.completions(completion(m, p))),
+    )
+    .await;
+    assert!(results.iter().all(Result::is_ok));
+    // [a, b] fills a batch; c and d wait out the window in separate batches.
+    assert_eq!(batches.load(Ordering::SeqCst), 3);
+}
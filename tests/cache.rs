@@ -0,0 +1,146 @@
+// File: cache.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for the response cache backends and for cache hits on the
+//     chat path: usage flags, receipts and settlement.
+//
+use std::time::Duration;
+
+use auria::{
+    cache::{CachedGeneration, DiskCache, MemoryCache, ResponseCache},
+    config::AppConfig,
+    models::ChatCompletionRequest,
+    settlement::SettlementBatch,
+    AuriaAgent,
+};
+use serde_json::json;
+
+fn chat(temperature: f32) -> ChatCompletionRequest {
+    serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "messages": [{ "role": "user", "content": "hi" }],
+        "max_tokens": 100,
+        "temperature": temperature,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn serves_deterministic_repeats_from_cache() {
+    let dir = std::env::temp_dir().join(format!("auria-cache-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        response_cache: "memory".to_string(),
+        cache_hit_price_microusdc_per_1k: 10,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+
+    let first = agent.chat_completions(chat(0.0)).await.unwrap();
+    let second = agent.chat_completions(chat(0.0)).await.unwrap();
+    assert_eq!(first.usage.cached, None);
+    assert_eq!(second.usage.cached, Some(true));
+    assert_eq!(
+        second.choices[0].message.content,
+        first.choices[0].message.content
+    );
+    assert_eq!(
+        agent
+            .chat_completions(chat(0.7))
+            .await
+            .unwrap()
+            .usage
+            .cached,
+        None
+    );
+
+    let receipts = agent.ledger().unwrap().receipts().unwrap();
+    assert_eq!(receipts.len(), 3);
+    let hit = receipts.iter().find(|r| r.request_id == second.id).unwrap();
+    assert_eq!(hit.cached, Some(true));
+    // NANO is 50 per 1k; hits are charged 10 per 1k.
+    assert_eq!(
+        hit.charge_microusdc,
+        Some((10 * hit.total_tokens as u64).div_ceil(1000))
+    );
+    assert_eq!(
+        receipts[0].charge_microusdc,
+        Some((50 * receipts[0].total_tokens as u64).div_ceil(1000))
+    );
+
+    let batch = SettlementBatch::from_receipts(1, &receipts);
+    assert_eq!(
+        batch.total_tokens,
+        (receipts[0].total_tokens + receipts[2].total_tokens) as u64
+    );
+}
+
+#[test]
+fn backends_expire_and_evict() {
+    let gens = vec![CachedGeneration {
+        node: "http://n/".to_string(),
+        tokens: vec!["a".to_string()],
+        completion_tokens: 1,
+    }];
+
+    let memory = MemoryCache::new(1, Duration::from_secs(60));
+    memory.put("k1", &gens);
+    memory.put("k2", &gens);
+    assert_eq!(memory.get("k1"), None);
+    assert_eq!(memory.get("k2"), Some(gens.clone()));
+
+    let dir = std::env::temp_dir().join(format!("auria-cache-{}", uuid::Uuid::new_v4()));
+    let disk = DiskCache::open(&dir, 8, Duration::from_secs(60)).unwrap();
+    disk.put("k1", &gens);
+    assert_eq!(
+        DiskCache::open(&dir, 8, Duration::from_secs(60))
+            .unwrap()
+            .get("k1"),
+        Some(gens.clone())
+    );
+
+    let expired = DiskCache::open(&dir, 8, Duration::ZERO).unwrap();
+    expired.put("k2", &gens);
+    assert_eq!(expired.get("k2"), None);
+    assert!(!dir.join("k2.json").exists());
+}
+
+#[test]
+fn disk_cache_is_bounded_and_swept() {
+    let gens = vec![CachedGeneration {
+        node: "http://n/".to_string(),
+        tokens: vec!["a".to_string()],
+        completion_tokens: 1,
+    }];
+    let dir = std::env::temp_dir().join(format!("auria-cache-{}", uuid::Uuid::new_v4()));
+    let entries = || std::fs::read_dir(&dir).unwrap().count();
+
+    // Going past 10 entries evicts the oldest, down to 9.
+    let disk = DiskCache::open(&dir, 10, Duration::from_secs(60)).unwrap();
+    for i in 0..11 {
+        disk.put(&format!("k{}", i), &gens);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(entries(), 9);
+    assert_eq!(disk.get("k0"), None);
+    assert_eq!(disk.get("k1"), None);
+    assert_eq!(disk.get("k10"), Some(gens.clone()));
+
+    // Opening sweeps expired entries and leftovers of failed writes.
+    let expired = DiskCache::open(&dir, 10, Duration::ZERO).unwrap();
+    expired.put("old", &gens);
+    let stale = dir.join("k.tmp");
+    std::fs::write(&stale, b"{").unwrap();
+    let old = std::time::SystemTime::now() - Duration::from_secs(120);
+    std::fs::File::options()
+        .write(true)
+        .open(&stale)
+        .unwrap()
+        .set_modified(old)
+        .unwrap();
+    DiskCache::open(&dir, 10, Duration::from_secs(60)).unwrap();
+    assert_eq!(entries(), 9);
+    assert!(!dir.join("old.json").exists());
+    assert!(!stale.exists());
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,246 @@
+// File: cancellation.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for cancelling node calls when a request is dropped, as on
+//     client disconnect, against mock nodes with and without cancel.
+//
+mod common;
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use auria::{config::AppConfig, models::CompletionRequest, AuriaAgent};
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+#[derive(Clone, Default)]
+struct Node {
+    /// Request ids the node started generating.
+    started: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Answers batches after two seconds, or fails every item at once when
+/// `fail`; cancelling a started request reports four tokens produced,
+/// unknown ids are 404.
+async fn mock_node(
+    node: Node,
+    cancels: mpsc::UnboundedSender<String>,
+    cancel: bool,
+    fail: bool,
+) -> String {
+    let started = node.started.clone();
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(move || async move { Json(json!({ "batch_generate": true, "cancel": cancel })) }),
+        )
+        .route(
+            "/v1/generate/batch",
+            post(move |Json(batch): Json<Value>| async move {
+                let requests = batch["requests"].as_array().unwrap().clone();
+                if fail {
+                    let results: Vec<Value> = requests
+                        .iter()
+                        .map(|_| json!({ "error": "out of memory" }))
+                        .collect();
+                    return Json(json!({ "results": results }));
+                }
+                for r in &requests {
+                    started
+                        .lock()
+                        .unwrap()
+                        .insert(r["request_id"].as_str().unwrap().to_string());
+                }
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                let results: Vec<Value> = requests
+                    .iter()
+                    .map(|_| json!({ "tokens": ["x"], "tokens_generated": 1 }))
+                    .collect();
+                Json(json!({ "results": results }))
+            }),
+        )
+        .route(
+            "/v1/generate/cancel",
+            post(move |Json(req): Json<Value>| async move {
+                let id = req["request_id"].as_str().unwrap().to_string();
+                cancels.send(id.clone()).unwrap();
+                match node.started.lock().unwrap().contains(&id) {
+                    true => (StatusCode::OK, Json(json!({ "tokens_generated": 4 }))),
+                    false => (
+                        StatusCode::NOT_FOUND,
+                        Json(json!({ "error": "unknown request" })),
+                    ),
+                }
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+async fn agent(window_ms: u64, node_urls: Vec<String>) -> AuriaAgent {
+    let dir = std::env::temp_dir().join(format!("auria-cancel-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls,
+        batch_window_ms: window_ms,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    agent
+}
+
+fn completion() -> CompletionRequest {
+    serde_json::from_value(json!({ "model": "AURIA:NANO", "prompt": "hello there" })).unwrap()
+}
+
+async fn wait_for_receipts(agent: &AuriaAgent) -> Vec<auria::receipts::UsageReceipt> {
+    let ledger = agent.ledger().unwrap();
+    let mut receipts = ledger.receipts().unwrap();
+    for _ in 0..50 {
+        if !receipts.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        receipts = ledger.receipts().unwrap();
+    }
+    receipts
+}
+
+#[tokio::test]
+async fn cancels_the_node_call_and_bills_what_it_produced() {
+    let (tx, mut cancels) = mpsc::unbounded_channel();
+    let node = Node::default();
+    let agent = agent(1, vec![mock_node(node.clone(), tx, true, false).await]).await;
+
+    let dropped =
+        tokio::time::timeout(Duration::from_millis(300), agent.completions(completion())).await;
+    assert!(dropped.is_err());
+    let id = tokio::time::timeout(Duration::from_secs(1), cancels.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(node.started.lock().unwrap().contains(&id));
+
+    let receipts = wait_for_receipts(&agent).await;
+    assert_eq!(receipts.len(), 1);
+    let r = &receipts[0];
+    assert_eq!(
+        (r.request_id.as_str(), r.cancelled),
+        (id.as_str(), Some(true))
+    );
+    assert_eq!(r.completion_tokens, 4);
+    assert!(r.prompt_tokens > 0);
+}
+
+#[tokio::test]
+async fn requests_dropped_in_the_batch_window_never_reach_the_node() {
+    let (tx, mut cancels) = mpsc::unbounded_channel();
+    let node = Node::default();
+    let agent = agent(200, vec![mock_node(node.clone(), tx, true, false).await]).await;
+
+    let dropped =
+        tokio::time::timeout(Duration::from_millis(50), agent.completions(completion())).await;
+    assert!(dropped.is_err());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // Nothing was sent, so there is nothing to cancel or bill.
+    assert!(node.started.lock().unwrap().is_empty());
+    assert!(cancels.try_recv().is_err());
+    assert!(agent.ledger().unwrap().receipts().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn nodes_without_cancel_are_billed_the_prompt_once_sent() {
+    let (tx, _cancels) = mpsc::unbounded_channel();
+    let node = Node::default();
+    let agent = agent(1, vec![mock_node(node.clone(), tx, false, false).await]).await;
+
+    let dropped =
+        tokio::time::timeout(Duration::from_millis(300), agent.completions(completion())).await;
+    assert!(dropped.is_err());
+
+    let receipts = wait_for_receipts(&agent).await;
+    assert_eq!(receipts.len(), 1);
+    let r = &receipts[0];
+    assert!(node.started.lock().unwrap().contains(&r.request_id));
+    assert_eq!(r.cancelled, Some(true));
+    assert_eq!(r.completion_tokens, 0);
+    assert!(r.prompt_tokens > 0);
+}
+
+#[tokio::test]
+async fn nodes_without_cancel_are_not_billed_for_the_batch_window() {
+    let (tx, _cancels) = mpsc::unbounded_channel();
+    let node = Node::default();
+    let agent = agent(200, vec![mock_node(node.clone(), tx, false, false).await]).await;
+
+    let dropped =
+        tokio::time::timeout(Duration::from_millis(50), agent.completions(completion())).await;
+    assert!(dropped.is_err());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(node.started.lock().unwrap().is_empty());
+    assert!(agent.ledger().unwrap().receipts().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn a_failed_sibling_call_is_not_billed_as_a_cancellation() {
+    let (tx, mut cancels) = mpsc::unbounded_channel();
+    let (slow, failing) = (Node::default(), Node::default());
+    let node_urls = vec![
+        mock_node(slow.clone(), tx.clone(), true, false).await,
+        mock_node(failing, tx, true, true).await,
+    ];
+    let agent = agent(1, node_urls).await;
+
+    let req = CompletionRequest {
+        n: Some(2),
+        ..completion()
+    };
+    let result = tokio::time::timeout(Duration::from_secs(1), agent.completions(req))
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    // The slow node is still asked to stop, but nothing is billed.
+    let id = tokio::time::timeout(Duration::from_secs(1), cancels.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(slow.started.lock().unwrap().contains(&id));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(agent.ledger().unwrap().receipts().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn node_calls_carry_their_receipt_ids() {
+    let (tx, _cancels) = mpsc::unbounded_channel();
+    let node = Node::default();
+    let agent = agent(1, vec![mock_node(node.clone(), tx, true, false).await]).await;
+
+    let req: CompletionRequest =
+        serde_json::from_value(json!({ "model": "AURIA:NANO", "prompt": ["one", "two"], "n": 2 }))
+            .unwrap();
+    let resp = agent.completions(req).await.unwrap();
+
+    let receipts: HashSet<String> = agent
+        .ledger()
+        .unwrap()
+        .receipts()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.request_id)
+        .collect();
+    let expected: HashSet<String> = (0..4).map(|i| format!("{}:{}", resp.id, i)).collect();
+    assert_eq!(receipts, expected);
+    assert_eq!(*node.started.lock().unwrap(), expected);
+}
@@ -0,0 +1,214 @@
+// File: chat_template.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for chat templates: built-in formats, selection per tier, per
+//     node capability and from template files, and token counts for
+//     prompts rendered with a node's own template.
+//
+mod common;
+
+use std::collections::BTreeMap;
+
+use auria::{
+    chat_template::{ChatTemplates, TemplateMessage},
+    config::{AppConfig, TierSpec},
+    models::{ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Tier},
+    tokenizer, AuriaAgent,
+};
+use axum::{routing::get, Json, Router};
+use serde_json::json;
+
+fn conversation() -> Vec<TemplateMessage> {
+    let messages: Vec<ChatMessage> = serde_json::from_value(json!([
+        { "role": "system", "content": "Be brief." },
+        { "role": "user", "content": [
+            { "type": "text", "text": "What is this? " },
+            { "type": "image_url", "image_url": { "url": "https://example.com/a.png" } },
+        ] },
+        { "role": "assistant", "content": null, "tool_calls": [
+            { "id": "call_1", "type": "function", "function": { "name": "look", "arguments": "{\"x\":1}" } },
+        ] },
+        { "role": "tool", "tool_call_id": "call_1", "content": "a cat" },
+    ]))
+    .unwrap();
+    messages.iter().map(TemplateMessage::from).collect()
+}
+
+#[test]
+fn renders_builtin_templates() {
+    let templates = ChatTemplates::load(&BTreeMap::new()).unwrap();
+    let msgs = conversation();
+    let call = r#"<tool_call>{"arguments":{"x":1},"name":"look"}</tool_call>"#;
+
+    assert_eq!(
+        templates.render("chatml", &msgs, true).unwrap(),
+        format!(
+            "<|im_start|>system\nBe brief.<|im_end|>\n<|im_start|>user\nWhat is this? <image><|im_end|>\n\
+             <|im_start|>assistant\n{call}<|im_end|>\n<|im_start|>tool\na cat<|im_end|>\n<|im_start|>assistant\n"
+        )
+    );
+    assert_eq!(
+        templates.render("llama3", &msgs[..2], false).unwrap(),
+        "<|begin_of_text|><|start_header_id|>system<|end_header_id|>\n\nBe brief.<|eot_id|>\
+         <|start_header_id|>user<|end_header_id|>\n\nWhat is this? <image><|eot_id|>"
+    );
+    assert!(templates.render("llama3", &msgs, true).unwrap().ends_with(
+        "<|start_header_id|>ipython<|end_header_id|>\n\na cat<|eot_id|><|start_header_id|>assistant<|end_header_id|>\n\n"
+    ));
+    assert_eq!(
+        templates.render("mistral", &msgs, true).unwrap(),
+        format!("<s>[INST] Be brief.\n\nWhat is this? <image>[/INST]{call}</s>[TOOL_RESULTS] a cat[/TOOL_RESULTS]")
+    );
+    assert_eq!(
+        templates.render("plain", &msgs[2..], true).unwrap(),
+        format!("assistant: {call}\ntool (call_1): a cat\n")
+    );
+    assert!(templates.render("nope", &msgs, true).is_err());
+}
+
+/// A node serving `tier` that advertises `template`; generation is stubbed.
+async fn template_node(tier: &'static str, template: &'static str) -> String {
+    let caps = json!({ "tiers": [tier], "chat_template": template });
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/v1/capabilities", get(move || async move { Json(caps) }));
+    let node = common::spawn_mock_node(app).await;
+    node
+}
+
+fn chat(model: &str) -> ChatCompletionRequest {
+    serde_json::from_value(
+        json!({ "model": model, "messages": [{ "role": "user", "content": "hi" }] }),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn selects_templates_by_tier_and_node() {
+    let path = std::env::temp_dir().join(format!("auria-template-{}.jinja", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        "{% for m in messages %}[{{ m.role }}] {{ m.content }}\n{% endfor %}\
+         {% if add_generation_prompt %}[assistant]{% endif %}",
+    )
+    .unwrap();
+
+    // The stub node echoes the prompt it was sent.
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "tiers": ["PRO"], "chat_template": "custom" })) }),
+        );
+    let node = common::spawn_mock_node(app).await;
+
+    let chatml = TierSpec {
+        chat_template: Some("chatml".to_string()),
+        ..AppConfig::default().tier_spec(Tier::Nano)
+    };
+    let cfg = AppConfig {
+        node_urls: vec![node],
+        chat_template_files: BTreeMap::from([(
+            "custom".to_string(),
+            path.to_string_lossy().into_owned(),
+        )]),
+        tier_specs: BTreeMap::from([(Tier::Nano, chatml)]),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    let text = |r: ChatCompletionResponse| {
+        r.choices[0]
+            .message
+            .content
+            .as_ref()
+            .unwrap()
+            .as_text()
+            .unwrap()
+            .to_string()
+    };
+
+    let nano = agent.chat_completions(chat("AURIA:NANO")).await.unwrap();
+    assert!(text(nano).contains("<|im_start|>user\nhi<|im_end|>\n<|im_start|>assistant\n"));
+    agent.check_nodes().await.unwrap();
+    let pro = agent.chat_completions(chat("AURIA:PRO")).await.unwrap();
+    assert!(text(pro).contains("[user] hi\n[assistant]"));
+
+    let unknown = AppConfig {
+        chat_template: "missing".to_string(),
+        ..cfg
+    };
+    assert!(AuriaAgent::new(unknown).await.is_err());
+}
+
+#[tokio::test]
+async fn counts_prompts_in_the_node_template() {
+    let path = std::env::temp_dir().join(format!("auria-template-{}.jinja", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        "one two three four five six {% for m in messages %}{{ m.content }} {% endfor %}",
+    )
+    .unwrap();
+    let files = BTreeMap::from([("verbose".to_string(), path.to_string_lossy().into_owned())]);
+    let cfg = AppConfig {
+        node_urls: vec![
+            template_node("PRO", "verbose").await,
+            template_node("NANO", "ghost").await,
+        ],
+        chat_template_files: files.clone(),
+        tokenizer: "whitespace".to_string(),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    let pro = agent.chat_completions(chat("AURIA:PRO")).await.unwrap();
+    let msgs = vec![TemplateMessage::new("user", "hi")];
+    let rendered = ChatTemplates::load(&files)
+        .unwrap()
+        .render("verbose", &msgs, true)
+        .unwrap();
+    assert_eq!(
+        pro.usage.prompt_tokens,
+        tokenizer::builtin("whitespace").unwrap().count(&rendered)
+    );
+    assert_eq!(pro.usage.prompt_tokens, 7);
+
+    // A template the gateway cannot render is ignored for the tier's.
+    let nano = agent.chat_completions(chat("AURIA:NANO")).await.unwrap();
+    let content = nano.choices[0].message.content.as_ref().unwrap();
+    assert!(content.as_text().unwrap().contains("] user: hi\n"));
+}
+
+#[tokio::test]
+async fn picks_one_node_per_request() {
+    let dir = std::env::temp_dir().join(format!("auria-template-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls: vec![
+            template_node("NANO", "chatml").await,
+            template_node("NANO", "chatml").await,
+        ],
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    // Round-robin alternates nodes between requests only if choosing the
+    // template and dispatching use the same pick.
+    for _ in 0..4 {
+        agent.chat_completions(chat("AURIA:NANO")).await.unwrap();
+    }
+    let nodes: Vec<String> = agent
+        .ledger()
+        .unwrap()
+        .receipts()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.node)
+        .collect();
+    assert_eq!(nodes.len(), 4);
+    assert_ne!(nodes[0], nodes[1]);
+    assert_eq!(nodes[0], nodes[2]);
+    assert_eq!(nodes[1], nodes[3]);
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,100 @@
+// File: choices.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for n > 1 chat choices and cost accounting across all
+//     generations.
+//
+use std::collections::BTreeMap;
+
+use auria::{
+    config::{AppConfig, TierSpec},
+    error::AgentError,
+    models::{ChatCompletionRequest, Tier},
+    policy::PolicyEngine,
+    AuriaAgent,
+};
+use serde_json::json;
+
+fn chat(n: u32) -> ChatCompletionRequest {
+    serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "messages": [{ "role": "user", "content": "hi" }],
+        "max_tokens": 100,
+        "n": n,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn returns_indexed_choices_with_aggregated_usage() {
+    let cfg = AppConfig {
+        node_urls: vec![
+            "http://127.0.0.1:1/".to_string(),
+            "http://127.0.0.1:2/".to_string(),
+        ],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+
+    let resp = agent.chat_completions(chat(3)).await.unwrap();
+    let indices: Vec<_> = resp.choices.iter().map(|c| c.index).collect();
+    assert_eq!(indices, vec![0, 1, 2]);
+    assert_eq!(resp.usage.completion_tokens, 3 * 3);
+    assert_eq!(resp.usage.total_tokens, resp.usage.prompt_tokens + 9);
+
+    assert!(matches!(
+        agent.chat_completions(chat(0)).await,
+        Err(AgentError::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn accounts_cost_for_every_generation() {
+    // NANO: 50 micro-USDC per 1k tokens; "user: hi\n" is 3 tokens + 100 max.
+    assert_eq!(PolicyEngine::estimate_cost(50, 103, 1), 6);
+
+    let cfg = AppConfig {
+        max_cost_microusdc: 12,
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    assert!(agent.chat_completions(chat(2)).await.is_ok());
+    assert!(matches!(
+        agent.chat_completions(chat(3)).await,
+        Err(AgentError::Permission(_))
+    ));
+}
+
+#[tokio::test]
+async fn receipts_add_up_to_reported_usage() {
+    // One micro-USDC per token, so charges are exact.
+    let spec = TierSpec {
+        price_microusdc_per_1k: 1000,
+        ..AppConfig::default().tier_spec(Tier::Nano)
+    };
+    let dir = std::env::temp_dir().join(format!("auria-choices-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        tier_specs: BTreeMap::from([(Tier::Nano, spec)]),
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+
+    let resp = agent.chat_completions(chat(3)).await.unwrap();
+    let receipts = agent.ledger().unwrap().receipts().unwrap();
+    assert_eq!(receipts.len(), 3);
+    let sum = |f: fn(&auria::receipts::UsageReceipt) -> u64| receipts.iter().map(f).sum::<u64>();
+    assert_eq!(
+        sum(|r| r.prompt_tokens as u64),
+        resp.usage.prompt_tokens as u64
+    );
+    assert_eq!(
+        sum(|r| r.total_tokens as u64),
+        resp.usage.total_tokens as u64
+    );
+    assert_eq!(
+        sum(|r| r.charge_microusdc.unwrap()),
+        resp.usage.total_tokens as u64
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
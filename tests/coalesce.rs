@@ -0,0 +1,123 @@
+// File: coalesce.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for single-flight coalescing of identical in-flight calls,
+//     and which agent requests are coalesced.
+//
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use auria::{
+    coalesce::SingleFlight, config::AppConfig, error::AgentError, models::CompletionRequest,
+    AuriaAgent,
+};
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+async fn slow(calls: &AtomicUsize, result: Result<u32, AgentError>) -> Result<u32, AgentError> {
+    calls.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    result
+}
+
+#[tokio::test]
+async fn concurrent_callers_share_one_call() {
+    let group = SingleFlight::<u32>::default();
+    let calls = AtomicUsize::new(0);
+
+    let runs = (0..5).map(|_| group.run("k", || slow(&calls, Ok(7))));
+    let results = futures::future::join_all(runs).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    let ran: Vec<bool> = results.iter().map(|r| r.as_ref().unwrap().1).collect();
+    assert_eq!(ran.iter().filter(|r| **r).count(), 1);
+    assert!(results.iter().all(|r| r.as_ref().unwrap().0 == 7));
+
+    // Errors are shared too; finished flights are not reused.
+    let failing = (0..2).map(|_| {
+        group.run("k", || {
+            slow(&calls, Err(AgentError::UpstreamTimeout("slow".to_string())))
+        })
+    });
+    let results = futures::future::join_all(failing).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(results
+        .iter()
+        .all(|r| matches!(r, Err(AgentError::UpstreamTimeout(_)))));
+}
+
+#[tokio::test]
+async fn waiter_takes_over_from_cancelled_caller() {
+    let group = Arc::new(SingleFlight::<u32>::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let first = {
+        let (group, calls) = (group.clone(), calls.clone());
+        tokio::spawn(async move { group.run("k", || slow(&calls, Ok(1))).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let second = {
+        let (group, calls) = (group.clone(), calls.clone());
+        tokio::spawn(async move { group.run("k", || slow(&calls, Ok(2))).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    first.abort();
+
+    assert_eq!(second.await.unwrap().unwrap(), (2, true));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        group.run("other", || async { Ok(3) }).await.unwrap(),
+        (3, true)
+    );
+}
+
+/// A batching node that counts the generations it is asked for.
+async fn counting_node(generated: Arc<AtomicUsize>) -> String {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/v1/capabilities", get(|| async { Json(json!({ "batch_generate": true })) }))
+        .route(
+            "/v1/generate/batch",
+            post(move |Json(batch): Json<Value>| async move {
+                let n = batch["requests"].as_array().unwrap().len();
+                generated.fetch_add(n, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Json(json!({ "results": vec![json!({ "tokens": ["x"], "tokens_generated": 1 }); n] }))
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+#[tokio::test]
+async fn coalesces_only_deterministic_requests() {
+    let generated = Arc::new(AtomicUsize::new(0));
+    let cfg = AppConfig {
+        node_urls: vec![counting_node(generated.clone()).await],
+        batch_window_ms: 1,
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    let twice = |temperature: f64| {
+        let req = json!({ "model": "AURIA:NANO", "prompt": "hi", "temperature": temperature });
+        let req: CompletionRequest = serde_json::from_value(req).unwrap();
+        futures::future::join(agent.completions(req.clone()), agent.completions(req))
+    };
+
+    let (a, b) = twice(1.0).await;
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(generated.load(Ordering::SeqCst), 2);
+
+    let (a, b) = twice(0.0).await;
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(generated.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,33 @@
+// File: mod.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Shared helpers for integration tests: mock nodes and gateways served
+//     on ephemeral local ports.
+//
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use auria::{api, config::AppConfig, AuriaAgent};
+use axum::Router;
+
+/// Serves `app` as a mock node and returns its base URL, `http://host:port/`.
+pub async fn spawn_mock_node(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/", addr)
+}
+
+/// Serves the gateway API for `agent` until the test ends and returns its
+/// base URL, `http://host:port`.
+pub async fn spawn_gateway(cfg: AppConfig, agent: AuriaAgent) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(api::serve_with_shutdown(
+        listener,
+        cfg,
+        agent,
+        std::future::pending(),
+    ));
+    format!("http://{}", addr)
+}
@@ -0,0 +1,141 @@
+// File: eip712.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     EIP-712 encoding and signing checked against the reference
+//     "Ether Mail" vectors from the EIP, plus settlement batch hashing.
+//
+use auria::eip712::{address_of, keccak256, TypedData};
+use auria::models::Tier;
+use auria::receipts::UsageReceipt;
+use auria::settlement::{merkle_root, SettlementBatch, SettlementDomain};
+
+fn mail() -> TypedData {
+    serde_json::from_value(serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn matches_eip712_reference_vectors() {
+    let td = mail();
+    assert_eq!(
+        td.encode_type("Mail").unwrap(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        hex::encode(td.type_hash("Mail").unwrap()),
+        "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+    );
+    assert_eq!(
+        hex::encode(td.hash_struct("Mail", &td.message).unwrap()),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(
+        hex::encode(td.domain_separator().unwrap()),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        hex::encode(td.signing_hash().unwrap()),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+    assert!(td.encode_type("Letter").is_err());
+    assert!(td.type_hash("Letter").is_err());
+}
+
+#[test]
+fn signs_like_eth_sign_typed_data() {
+    let key = k256::ecdsa::SigningKey::from_slice(&keccak256(b"cow")).unwrap();
+    assert_eq!(
+        address_of(key.verifying_key()),
+        "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+    );
+
+    let sig = mail().sign(&key).unwrap();
+    assert_eq!(sig.v, 28);
+    assert_eq!(
+        sig.r,
+        "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"
+    );
+    assert_eq!(
+        sig.s,
+        "0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"
+    );
+}
+
+#[test]
+fn settlement_batch_is_order_independent() {
+    let receipt = |id: &str, node: &str, tokens: u32| UsageReceipt {
+        request_id: id.to_string(),
+        created: 100,
+        model: "AURIA:STANDARD".to_string(),
+        tier: Tier::Standard,
+        node: node.to_string(),
+        prompt_tokens: 0,
+        completion_tokens: tokens,
+        total_tokens: tokens,
+        ..Default::default()
+    };
+    let rs = vec![
+        receipt("a", "http://n1/", 3),
+        receipt("b", "http://n2/", 5),
+        receipt("c", "http://n1/", 7),
+    ];
+    let mut reversed = rs.clone();
+    reversed.reverse();
+
+    let batch = SettlementBatch::from_receipts(1, &rs);
+    assert_eq!(
+        batch.root,
+        SettlementBatch::from_receipts(1, &reversed).root
+    );
+    assert_eq!(batch.total_tokens, 15);
+    assert_eq!(batch.nodes.iter().map(|n| n.tokens).sum::<u64>(), 15);
+
+    let td = batch.typed_data(&SettlementDomain {
+        chain_id: 8453,
+        verifying_contract: "0x0000000000000000000000000000000000000001".to_string(),
+    });
+    assert_eq!(
+        td.encode_type("SettlementBatch").unwrap(),
+        "SettlementBatch(uint64 epoch,bytes32 root,uint256 totalTokens,NodeTotal[] nodes)\
+         NodeTotal(bytes32 node,uint64 receipts,uint256 tokens)"
+    );
+    // Cross-checked with an independent eth_signTypedData_v4 implementation
+    // that reproduces the Mail digest above.
+    assert_eq!(
+        hex::encode(td.signing_hash().unwrap()),
+        "bac6771f1f6a7afd54c3952c3493808ed3c0466a529a61106a1a187f717361ce"
+    );
+
+    assert_eq!(merkle_root(&[]), [0u8; 32]);
+    assert_eq!(merkle_root(&[[1u8; 32]]), [1u8; 32]);
+}
@@ -0,0 +1,131 @@
+// File: embeddings.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for /v1/embeddings routing, dimension truncation, base64
+//     encoding and admission control against a mock embedding node.
+//
+mod common;
+
+use std::{collections::BTreeMap, time::Duration};
+
+use auria::{
+    config::AppConfig,
+    embeddings::{self, EncodingFormat},
+    error::AgentError,
+    models::{EmbeddingRequest, EmbeddingVector, Tier},
+    AuriaAgent,
+};
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use base64::Engine;
+use serde_json::{json, Value};
+
+async fn mock_node(delay: Duration) -> String {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "embeddings": true, "embedding_dimensions": 3 })) }),
+        )
+        .route(
+            "/v1/embeddings",
+            post(move |Json(req): Json<Value>| async move {
+                tokio::time::sleep(delay).await;
+                let n = req["input"].as_array().map(Vec::len).unwrap_or(0);
+                Json(json!({ "embeddings": vec![[3.0, 4.0, 12.0]; n], "prompt_tokens": 99 }))
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+#[test]
+fn truncates_and_renormalises() {
+    let v = embeddings::truncate(vec![3.0, 4.0, 12.0], 2).unwrap();
+    assert_eq!(v, vec![0.6, 0.8]);
+    assert!(embeddings::truncate(vec![1.0], 2).is_err());
+
+    let EmbeddingVector::Base64(b) = embeddings::encode(vec![1.0], EncodingFormat::Base64) else {
+        panic!("expected base64");
+    };
+    assert_eq!(
+        base64::engine::general_purpose::STANDARD.decode(b).unwrap(),
+        1.0f32.to_le_bytes()
+    );
+}
+
+#[tokio::test]
+async fn routes_only_to_embedding_nodes() {
+    let req: EmbeddingRequest = serde_json::from_value(
+        json!({ "model": "AURIA:NANO", "input": ["a", "b"], "dimensions": 2 }),
+    )
+    .unwrap();
+
+    // Default capabilities do not include embeddings.
+    let stub = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    assert!(stub.embeddings(req.clone()).await.is_err());
+
+    let cfg = AppConfig {
+        node_urls: vec![mock_node(Duration::ZERO).await],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    let resp = agent.embeddings(req).await.unwrap();
+    assert_eq!(resp.data.len(), 2);
+    assert_eq!(resp.data[1].index, 1);
+    assert!(matches!(&resp.data[0].embedding, EmbeddingVector::Float(v) if v == &vec![0.6, 0.8]));
+    assert_eq!(resp.usage.prompt_tokens, 2);
+}
+
+#[tokio::test]
+async fn embeddings_count_against_tier_limits() {
+    let cfg = AppConfig {
+        node_urls: vec![mock_node(Duration::from_millis(300)).await],
+        tier_concurrency: BTreeMap::from([(Tier::Nano, 1)]),
+        admission_queue_size: 0,
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    let req: EmbeddingRequest =
+        serde_json::from_value(json!({ "model": "AURIA:NANO", "input": "a" })).unwrap();
+
+    let first = tokio::spawn({
+        let (agent, req) = (agent.clone(), req.clone());
+        async move { agent.embeddings(req).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let caller = agent.caller(None, None);
+    assert!(matches!(
+        agent.embeddings_for(&caller, req.clone()).await,
+        Err(AgentError::RateLimit(..))
+    ));
+
+    first.await.unwrap().unwrap();
+    agent.embeddings_for(&caller, req).await.unwrap();
+}
+
+#[tokio::test]
+async fn checks_dimensions_before_calling_the_node() {
+    // The node would take a minute; the request must fail without it.
+    let cfg = AppConfig {
+        node_urls: vec![mock_node(Duration::from_secs(60)).await],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    for dimensions in [0, 4] {
+        let req: EmbeddingRequest = serde_json::from_value(
+            json!({ "model": "AURIA:NANO", "input": "a", "dimensions": dimensions }),
+        )
+        .unwrap();
+        let err = tokio::time::timeout(Duration::from_secs(5), agent.embeddings(req))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, AgentError::InvalidRequest(ref m) if m.contains("dimensions")));
+    }
+}
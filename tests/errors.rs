@@ -0,0 +1,141 @@
+// File: errors.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for AgentError classification, its OpenAI-shaped HTTP
+//     rendering and request ids on every response.
+//
+mod common;
+
+use auria::{config::AppConfig, error::AgentError, models::EmbeddingRequest, AuriaAgent};
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn renders_openai_error_with_request_id() {
+    let resp = AgentError::RateLimit("slow down".to_string(), None)
+        .with_request_id("req_1")
+        .into_response();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()["x-request-id"], "req_1");
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"]["type"], "rate_limit_error");
+    assert_eq!(body["error"]["code"], "rate_limit_exceeded");
+    assert_eq!(body["error"]["request_id"], "req_1");
+}
+
+#[tokio::test]
+async fn classifies_agent_failures() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    let req: EmbeddingRequest =
+        serde_json::from_value(serde_json::json!({ "model": "AURIA:NANO", "input": "a" })).unwrap();
+
+    let err = agent.embeddings(req.clone()).await.unwrap_err();
+    assert!(matches!(err, AgentError::NoCapacity(..)));
+    assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(err.is_retryable());
+
+    let bad = EmbeddingRequest {
+        encoding_format: Some("int8".to_string()),
+        ..req.clone()
+    };
+    let err = agent.embeddings(bad).await.unwrap_err();
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    assert!(!err.is_retryable());
+
+    // A node answering with the wrong number of vectors is at fault, not
+    // the agent.
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "embeddings": true })) }),
+        )
+        .route(
+            "/v1/embeddings",
+            post(|| async { Json(json!({ "embeddings": [], "prompt_tokens": 1 })) }),
+        );
+    let cfg = AppConfig {
+        node_urls: vec![common::spawn_mock_node(app).await],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    let err = agent.embeddings(req).await.unwrap_err();
+    assert_eq!(err.status(), StatusCode::BAD_GATEWAY);
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn every_response_carries_the_request_id() {
+    let dir = std::env::temp_dir().join(format!("auria-errors-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    let base = common::spawn_gateway(cfg, agent.clone()).await;
+    let http = reqwest::Client::new();
+    let request_id =
+        |r: &reqwest::Response| r.headers()["x-request-id"].to_str().unwrap().to_string();
+
+    // The header names the response and its receipt.
+    let chat = json!({ "model": "AURIA:NANO", "messages": [{ "role": "user", "content": "hi" }] });
+    let resp = http
+        .post(format!("{}/v1/chat/completions", base))
+        .json(&chat)
+        .send()
+        .await
+        .unwrap();
+    let id = request_id(&resp);
+    assert_eq!(resp.json::<Value>().await.unwrap()["id"], id.as_str());
+    assert!(agent.ledger().unwrap().find(&id).unwrap().is_some());
+
+    let mut stream = chat.clone();
+    stream["stream"] = json!(true);
+    let resp = http
+        .post(format!("{}/v1/chat/completions", base))
+        .json(&stream)
+        .send()
+        .await
+        .unwrap();
+    let id = request_id(&resp);
+    assert!(resp.text().await.unwrap().contains(&id));
+
+    let messages = json!({ "model": "AURIA:NANO", "max_tokens": 16, "messages": [{ "role": "user", "content": "hi" }] });
+    let resp = http
+        .post(format!("{}/v1/messages", base))
+        .json(&messages)
+        .send()
+        .await
+        .unwrap();
+    let id = request_id(&resp);
+    assert_eq!(resp.json::<Value>().await.unwrap()["id"], id.as_str());
+
+    let resp = http
+        .get(format!("{}/v1/models", base))
+        .send()
+        .await
+        .unwrap();
+    assert!(!request_id(&resp).is_empty());
+
+    let resp = http
+        .get(format!("{}/v1/models/nope", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let id = request_id(&resp);
+    assert_eq!(
+        resp.json::<Value>().await.unwrap()["error"]["request_id"],
+        id.as_str()
+    );
+}
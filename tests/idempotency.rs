@@ -0,0 +1,144 @@
+// File: idempotency.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for Idempotency-Key claims: waiting duplicates, replays,
+//     body mismatches, failed first requests, expiry, the capacity bound
+//     and key scoping over HTTP.
+//
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use auria::{
+    config::AppConfig,
+    error::AgentError,
+    idempotency::{Claim, IdempotencyStore},
+    AuriaAgent,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn duplicates_wait_for_and_replay_the_first_response() {
+    let store = Arc::new(IdempotencyStore::<String>::new(Duration::from_secs(60)));
+    let Claim::Run(lease) = store.claim("key-a", "k1", "body").await.unwrap() else {
+        panic!("first claim must run")
+    };
+
+    let waiter = {
+        let store = store.clone();
+        tokio::spawn(async move {
+            match store.claim("key-a", "k1", "body").await.unwrap() {
+                Claim::Replay(r) => r,
+                Claim::Run(_) => panic!("duplicate must not run"),
+            }
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!waiter.is_finished());
+    lease.complete("resp-1".to_string());
+    assert_eq!(waiter.await.unwrap(), "resp-1");
+
+    assert!(
+        matches!(store.claim("key-a", "k1", "body").await.unwrap(), Claim::Replay(r) if r == "resp-1")
+    );
+    assert!(matches!(
+        store.claim("key-a", "k1", "other").await,
+        Err(AgentError::IdempotencyMismatch(_))
+    ));
+    // Keys are scoped per API key.
+    assert!(matches!(
+        store.claim("key-b", "k1", "other").await.unwrap(),
+        Claim::Run(_)
+    ));
+}
+
+#[tokio::test]
+async fn failed_requests_release_and_responses_expire() {
+    let store = IdempotencyStore::<String>::new(Duration::from_millis(50));
+    match store.claim("", "k1", "body").await.unwrap() {
+        Claim::Run(lease) => drop(lease),
+        Claim::Replay(_) => panic!("nothing stored yet"),
+    }
+    let Claim::Run(lease) = store.claim("", "k1", "body").await.unwrap() else {
+        panic!("released key must run")
+    };
+    lease.complete("resp".to_string());
+    assert!(matches!(
+        store.claim("", "k1", "body").await.unwrap(),
+        Claim::Replay(_)
+    ));
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(matches!(
+        store.claim("", "k1", "other").await.unwrap(),
+        Claim::Run(_)
+    ));
+    assert!(store.claim("", "", "body").await.is_err());
+}
+
+#[tokio::test]
+async fn evicts_least_recently_used_keys_and_sweeps_expired_ones() {
+    let store = IdempotencyStore::<String>::with_capacity(Duration::from_millis(50), 2);
+    for key in ["k1", "k2", "k3"] {
+        let Claim::Run(lease) = store.claim("", key, "body").await.unwrap() else {
+            panic!("new key must run")
+        };
+        lease.complete(key.to_string());
+    }
+    assert_eq!(store.len(), 2);
+    assert!(matches!(
+        store.claim("", "k3", "body").await.unwrap(),
+        Claim::Replay(_)
+    ));
+    assert!(matches!(
+        store.claim("", "k1", "body").await.unwrap(),
+        Claim::Run(_)
+    ));
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    store.sweep();
+    assert!(store.is_empty());
+}
+
+#[tokio::test]
+async fn scopes_keys_by_api_key_over_http() {
+    let cfg = AppConfig::default();
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    let url = format!(
+        "{}/v1/chat/completions",
+        common::spawn_gateway(cfg, agent).await
+    );
+
+    let http = reqwest::Client::new();
+    let send = |api_key: Option<&str>, content: &str| {
+        let body =
+            json!({ "model": "AURIA:NANO", "messages": [{ "role": "user", "content": content }] });
+        let req = http.post(&url).header("idempotency-key", "k1").json(&body);
+        match api_key {
+            Some(k) => req.bearer_auth(k),
+            None => req,
+        }
+        .send()
+    };
+    let replayed = |r: &reqwest::Response| r.headers().contains_key("idempotent-replayed");
+
+    // Anonymous requests never replay each other.
+    assert!(!replayed(&send(None, "hi").await.unwrap()));
+    assert!(!replayed(&send(None, "hi").await.unwrap()));
+
+    let first = send(Some("sk-a"), "hi").await.unwrap();
+    assert!(!replayed(&first));
+    let replay = send(Some("sk-a"), "hi").await.unwrap();
+    assert!(replayed(&replay));
+    // A replay names the request whose receipt it repeats.
+    assert_eq!(
+        replay.headers()["x-request-id"],
+        first.headers()["x-request-id"]
+    );
+    let reused = send(Some("sk-a"), "bye").await.unwrap();
+    assert_eq!(reused.status(), 422);
+    assert_eq!(
+        reused.json::<serde_json::Value>().await.unwrap()["error"]["code"],
+        "idempotency_key_reused"
+    );
+}
@@ -0,0 +1,59 @@
+// File: keystore.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for the encrypted agent keystore: persistence, passphrase
+//     checks and key rotation.
+//
+use auria::keystore::{KeyAlgorithm, Keystore};
+
+fn temp_path() -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("auria-keystore-{}", uuid::Uuid::new_v4()))
+        .join("keys.json")
+}
+
+#[test]
+fn keystore_round_trip_and_rotation() {
+    let path = temp_path();
+    let mut ks = Keystore::open_or_create(&path, "secret").unwrap();
+    let first = ks.generate(KeyAlgorithm::Ed25519).unwrap();
+    ks.generate(KeyAlgorithm::Secp256k1).unwrap();
+    assert!(ks.generate(KeyAlgorithm::Ed25519).is_err());
+    ks.save().unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    assert!(Keystore::open(&path, "wrong").is_err());
+
+    let mut ks = Keystore::open(&path, "secret").unwrap();
+    let second = ks.rotate(KeyAlgorithm::Ed25519).unwrap();
+    assert_ne!(first.public_key, second.public_key);
+
+    let keys = ks.public_keys().unwrap();
+    assert_eq!(keys.len(), 3);
+    assert!(keys.iter().any(|k| k.id == first.id && k.retired.is_some()));
+
+    let id = ks.identity().unwrap();
+    assert_eq!(id.ed25519_public_hex().unwrap(), second.public_key);
+    assert!(id.secp256k1().is_some());
+}
+
+#[test]
+fn imports_known_secret() {
+    let mut ks = Keystore::open_or_create(temp_path(), "secret").unwrap();
+    // RFC 8032 test vector 1.
+    let info = ks
+        .import(
+            KeyAlgorithm::Ed25519,
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        )
+        .unwrap();
+    assert_eq!(
+        info.public_key,
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+    );
+}
@@ -0,0 +1,59 @@
+// File: models.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for the model catalogue and the legacy completions endpoint.
+//
+use auria::{
+    config::AppConfig,
+    models::{CompletionRequest, Tier},
+    AuriaAgent,
+};
+
+#[tokio::test]
+async fn lists_tiers_and_aliases() {
+    let mut cfg = AppConfig::default();
+    cfg.model_aliases
+        .insert("gpt-4o-mini".to_string(), Tier::Standard);
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+
+    let ids: Vec<_> = agent.list_models().into_iter().map(|m| m.id).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "AURIA:NANO",
+            "AURIA:STANDARD",
+            "AURIA:PRO",
+            "AURIA:MAX",
+            "gpt-4o-mini"
+        ]
+    );
+
+    let alias = agent.model("gpt-4o-mini").unwrap();
+    assert_eq!(alias.alias_of.as_deref(), Some("AURIA:STANDARD"));
+    assert_eq!(alias.context_window, 32_768);
+    assert_eq!(agent.model("AURIA:PRO").unwrap().tier, Tier::Pro);
+    assert!(agent.model("gpt-5").is_none());
+}
+
+#[tokio::test]
+async fn legacy_completions_skip_chat_templating() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    let req: CompletionRequest = serde_json::from_value(serde_json::json!({
+        "model": "AURIA:NANO",
+        "prompt": ["Once upon", "a time"],
+        "echo": true,
+    }))
+    .unwrap();
+
+    let resp = agent.completions(req).await.unwrap();
+    assert_eq!(resp.object, "text_completion");
+    assert_eq!(resp.choices.len(), 2);
+    assert!(resp.choices[1].text.starts_with("a time"));
+    assert!(!resp.choices[0].text.contains("user: "));
+
+    let best_of: CompletionRequest = serde_json::from_value(
+        serde_json::json!({ "model": "AURIA:NANO", "prompt": "x", "best_of": 3 }),
+    )
+    .unwrap();
+    assert!(agent.completions(best_of).await.is_err());
+}
@@ -0,0 +1,155 @@
+// File: multimodal.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for multimodal chat content: parsing, data URL limits,
+//     request body limits and routing of image requests by tier and node
+//     capability.
+//
+mod common;
+
+use auria::{
+    config::AppConfig,
+    error::AgentError,
+    models::{ChatCompletionRequest, ContentPart, MessageContent},
+    multimodal::{self, ImageInput, ImageLimits},
+    AuriaAgent,
+};
+use axum::{routing::get, Json, Router};
+use base64::Engine;
+use serde_json::{json, Value};
+
+const PIXEL: &str = "data:image/png;base64,iVBORw0KGgo=";
+
+fn vision(model: &str, url: &str) -> ChatCompletionRequest {
+    serde_json::from_value(json!({
+        "model": model,
+        "messages": [{ "role": "user", "content": [
+            { "type": "text", "text": "What is this?" },
+            { "type": "image_url", "image_url": { "url": url } },
+        ]}],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn parses_parts_and_enforces_limits() {
+    let req = vision("AURIA:PRO", PIXEL);
+    let Some(MessageContent::Parts(parts)) = &req.messages[0].content else {
+        panic!("expected parts")
+    };
+    assert!(matches!(&parts[1], ContentPart::ImageUrl { .. }));
+
+    let limits = ImageLimits {
+        max_bytes: 8,
+        max_images: 1,
+    };
+    let images = multimodal::collect_images(&req.messages, limits).unwrap();
+    assert!(
+        matches!(&images[0], ImageInput::Base64 { media_type, .. } if media_type == "image/png")
+    );
+
+    let tight = ImageLimits {
+        max_bytes: 4,
+        max_images: 1,
+    };
+    assert!(multimodal::collect_images(&req.messages, tight).is_err());
+    let text_url = vision("AURIA:PRO", "data:text/plain;base64,aGk=");
+    assert!(multimodal::collect_images(&text_url.messages, limits).is_err());
+}
+
+#[tokio::test]
+async fn routes_images_to_multimodal_nodes_only() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    let text_only = agent
+        .chat_completions(vision("AURIA:NANO", PIXEL))
+        .await
+        .unwrap_err();
+    assert!(matches!(text_only, AgentError::InvalidRequest(ref m) if m.contains("text-only")));
+    assert!(matches!(
+        agent.chat_completions(vision("AURIA:PRO", PIXEL)).await,
+        Err(AgentError::NoCapacity(..))
+    ));
+
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "multimodal": true })) }),
+        );
+    let node = common::spawn_mock_node(app).await;
+
+    let cfg = AppConfig {
+        node_urls: vec![node],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    assert!(agent
+        .chat_completions(vision("AURIA:PRO", PIXEL))
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn accepts_bodies_up_to_the_image_limits() {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "multimodal": true })) }),
+        );
+    let node = common::spawn_mock_node(app).await;
+
+    let cfg = AppConfig {
+        node_urls: vec![node],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    let url = format!(
+        "{}/v1/chat/completions",
+        common::spawn_gateway(cfg, agent).await
+    );
+
+    let http = reqwest::Client::new();
+    let image = |bytes: usize| {
+        let data = base64::engine::general_purpose::STANDARD.encode(vec![0u8; bytes]);
+        vision("AURIA:PRO", &format!("data:image/png;base64,{}", data))
+    };
+    let ok = http
+        .post(&url)
+        .json(&image(3 * 1024 * 1024))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ok.status(), 200);
+
+    // Over max_image_bytes (5 MiB): the image limit answers, not axum's 413.
+    let big = http
+        .post(&url)
+        .json(&image(6 * 1024 * 1024))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(big.status(), 400);
+    let body: Value = big.json().await.unwrap();
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("the limit is 5242880"));
+
+    // Routes without images keep the default body limit.
+    let prompt = "x".repeat(3 * 1024 * 1024);
+    let completion = http
+        .post(url.replace("chat/completions", "completions"))
+        .json(&json!({ "model": "AURIA:PRO", "prompt": prompt }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(completion.status(), 400);
+    let body: Value = completion.json().await.unwrap();
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("length limit exceeded"));
+}
@@ -0,0 +1,200 @@
+// File: openapi.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Drift checks between the OpenAPI description, the router and the
+//     bodies the handlers actually serialize.
+//
+use auria::{
+    anthropic::{self, MessagesRequest},
+    api::{self, ApiDoc},
+    config::AppConfig,
+    error::AgentError,
+    models::{ChatCompletionRequest, CompletionRequest, ModelList},
+    AuriaAgent,
+};
+use axum::response::IntoResponse;
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use utoipa::OpenApi;
+
+/// Validates `value` against component `name` and fails on any object
+/// key the schema does not declare.
+fn assert_conforms(doc: &Value, name: &str, value: &Value) {
+    let root = json!({ "$ref": format!("#/components/schemas/{}", name), "components": doc["components"] });
+    let compiled = JSONSchema::compile(&root).unwrap();
+    if let Err(errors) = compiled.validate(value) {
+        let msgs: Vec<String> = errors
+            .map(|e| format!("{} at '{}'", e, e.instance_path))
+            .collect();
+        panic!("{} does not match its schema: {}", name, msgs.join("; "));
+    }
+    assert_declared(
+        doc,
+        &json!({ "$ref": format!("#/components/schemas/{}", name) }),
+        value,
+        name,
+    );
+}
+
+fn assert_declared(doc: &Value, schema: &Value, value: &Value, at: &str) {
+    let schema = resolve(doc, schema);
+    let mut branches = vec![schema];
+    for key in ["allOf", "oneOf", "anyOf"] {
+        if let Some(list) = schema[key].as_array() {
+            branches.extend(list.iter().map(|s| resolve(doc, s)));
+        }
+    }
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                let props: Vec<&Value> = branches
+                    .iter()
+                    .filter_map(|b| b["properties"].get(k))
+                    .collect();
+                if props.is_empty() && branches.iter().all(|b| b["additionalProperties"].is_null())
+                {
+                    // Untagged and tagged enums are checked by validation.
+                    if branches.iter().any(|b| b["oneOf"].is_array()) {
+                        continue;
+                    }
+                    panic!("{}.{} is not declared in the schema", at, k);
+                }
+                for p in props {
+                    assert_declared(doc, p, v, &format!("{}.{}", at, k));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                for b in &branches {
+                    if b["items"].is_object() {
+                        assert_declared(doc, &b["items"], item, &format!("{}[{}]", at, i));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve<'a>(doc: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema["$ref"]
+        .as_str()
+        .and_then(|r| r.strip_prefix("#/components/schemas/"))
+    {
+        Some(name) => resolve(doc, &doc["components"]["schemas"][name]),
+        None => schema,
+    }
+}
+
+#[test]
+fn documents_every_route() {
+    let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    assert!(doc["openapi"].as_str().unwrap().starts_with("3.1"));
+    assert_eq!(
+        doc["components"]["securitySchemes"]["bearerAuth"]["scheme"],
+        "bearer"
+    );
+
+    let paths = doc["paths"].as_object().unwrap();
+    let routes = api::routes();
+    for (method, path) in &routes {
+        let openapi_path = path.split('/').map(|s| match s.strip_prefix(':') {
+            Some(param) => format!("{{{}}}", param),
+            None => s.to_string(),
+        });
+        let openapi_path = openapi_path.collect::<Vec<_>>().join("/");
+        let op = &paths
+            .get(&openapi_path)
+            .unwrap_or_else(|| panic!("{} is not documented", path))
+            [method.as_str().to_lowercase()];
+        assert!(op.is_object(), "{} {} is not documented", method, path);
+        assert!(
+            op["responses"]["200"].is_object(),
+            "{} {} has no success response",
+            method,
+            path
+        );
+    }
+    let operations: usize = paths.values().map(|p| p.as_object().unwrap().len()).sum();
+    assert_eq!(
+        operations,
+        routes.len(),
+        "documented operations without a route"
+    );
+}
+
+#[tokio::test]
+async fn schemas_match_served_bodies() {
+    let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+
+    let models = ModelList {
+        object: "list".to_string(),
+        data: agent.list_models(),
+    };
+    assert_conforms(&doc, "ModelList", &serde_json::to_value(models).unwrap());
+
+    let chat: ChatCompletionRequest = serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "messages": [{ "role": "user", "content": "hi" }],
+        "stream_options": { "include_usage": true },
+    }))
+    .unwrap();
+    let resp = agent.chat_completions(chat.clone()).await.unwrap();
+    assert_conforms(
+        &doc,
+        "ChatCompletionResponse",
+        &serde_json::to_value(resp).unwrap(),
+    );
+    for chunk in agent.chat_completion_chunks(chat).await.unwrap() {
+        assert_conforms(
+            &doc,
+            "ChatCompletionChunk",
+            &serde_json::to_value(chunk).unwrap(),
+        );
+    }
+
+    let completion: CompletionRequest = serde_json::from_value(
+        json!({ "model": "AURIA:NANO", "prompt": ["a", "b"], "echo": true }),
+    )
+    .unwrap();
+    let resp = agent.completions(completion).await.unwrap();
+    assert_conforms(
+        &doc,
+        "CompletionResponse",
+        &serde_json::to_value(resp).unwrap(),
+    );
+
+    let message: MessagesRequest = serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "max_tokens": 64,
+        "messages": [{ "role": "user", "content": "hi" }],
+    }))
+    .unwrap();
+    let resp = agent
+        .chat_completions(anthropic::to_chat_request(message).unwrap())
+        .await
+        .unwrap();
+    assert_conforms(
+        &doc,
+        "MessagesResponse",
+        &serde_json::to_value(anthropic::from_chat_response(resp)).unwrap(),
+    );
+
+    let err = AgentError::NoCapacity("no healthy node".to_string(), None);
+    assert_conforms(
+        &doc,
+        "AnthropicErrorResponse",
+        &serde_json::to_value(anthropic::error_body(&err)).unwrap(),
+    );
+    let resp = err.with_request_id("req_1").into_response();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_conforms(
+        &doc,
+        "ErrorResponse",
+        &serde_json::from_slice(&bytes).unwrap(),
+    );
+}
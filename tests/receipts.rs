@@ -0,0 +1,124 @@
+// File: receipts.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for the receipt ledger, settlement outbox, receipt
+//     signature verification and CSV export.
+//
+use auria::config::PromptMode;
+use auria::models::Tier;
+use auria::policy::AppliedPrompt;
+use auria::receipts::{self, Ledger, OutboxEntry, SettlementStatus, UsageReceipt, VerifyOutcome};
+use ed25519_dalek::{Signer, SigningKey};
+
+fn receipt(id: &str, created: i64) -> UsageReceipt {
+    UsageReceipt {
+        request_id: id.to_string(),
+        created,
+        model: "AURIA:STANDARD".to_string(),
+        tier: Tier::Standard,
+        node: "http://127.0.0.1:8080/".to_string(),
+        prompt_tokens: 4,
+        completion_tokens: 3,
+        total_tokens: 7,
+        ..Default::default()
+    }
+}
+
+fn temp_ledger() -> Ledger {
+    let dir = std::env::temp_dir().join(format!("auria-receipts-{}", uuid::Uuid::new_v4()));
+    Ledger::open(dir).unwrap()
+}
+
+#[test]
+fn ledger_round_trip_and_range() {
+    let ledger = temp_ledger();
+    ledger.append(&receipt("a", 100)).unwrap();
+    ledger.append(&receipt("b", 200)).unwrap();
+    ledger.append(&receipt("c", 300)).unwrap();
+
+    assert_eq!(ledger.receipts().unwrap().len(), 3);
+    assert_eq!(ledger.find("b").unwrap().unwrap().created, 200);
+
+    let ids: Vec<_> = ledger
+        .range(Some(200), Some(300))
+        .unwrap()
+        .into_iter()
+        .map(|r| r.request_id)
+        .collect();
+    assert_eq!(ids, vec!["b"]);
+}
+
+#[test]
+fn outbox_latest_status_wins() {
+    let ledger = temp_ledger();
+    ledger.append(&receipt("a", 100)).unwrap();
+    ledger
+        .update_status(&OutboxEntry {
+            request_id: "a".to_string(),
+            status: SettlementStatus::Settled,
+            batch: Some("epoch-1".to_string()),
+            updated: 150,
+        })
+        .unwrap();
+
+    let status = ledger.settlement_status().unwrap();
+    assert_eq!(status["a"].status, SettlementStatus::Settled);
+    assert_eq!(ledger.outbox().unwrap().len(), 2);
+}
+
+#[test]
+fn verifies_receipt_signatures() {
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let mut r = receipt("a", 100);
+    let trusted = vec![hex::encode(key.verifying_key().to_bytes())];
+    assert_eq!(r.verify(&trusted).unwrap(), VerifyOutcome::Unsigned);
+
+    r.signer = Some(trusted[0].clone());
+    r.signature = Some(hex::encode(key.sign(&r.signing_payload()).to_bytes()));
+    assert_eq!(r.verify(&trusted).unwrap(), VerifyOutcome::Valid);
+
+    // A receipt minted with someone else's key verifies against itself only.
+    let mut forged = r.clone();
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    forged.signer = Some(hex::encode(other.verifying_key().to_bytes()));
+    forged.signature = Some(hex::encode(
+        other.sign(&forged.signing_payload()).to_bytes(),
+    ));
+    assert_eq!(forged.verify(&trusted).unwrap(), VerifyOutcome::Untrusted);
+
+    r.completion_tokens += 1;
+    assert_eq!(r.verify(&trusted).unwrap(), VerifyOutcome::Invalid);
+}
+
+#[test]
+fn exports_every_column_to_csv() {
+    let mut cached = receipt("a", 100);
+    cached.cached = Some(true);
+    cached.charge_microusdc = Some(12);
+    let mut guarded = receipt("b", 200);
+    guarded.policy_prompts = vec![
+        AppliedPrompt {
+            name: "one".to_string(),
+            mode: PromptMode::Prepend,
+            sha3_256: "aa".to_string(),
+        },
+        AppliedPrompt {
+            name: "two".to_string(),
+            mode: PromptMode::Replace,
+            sha3_256: "bb".to_string(),
+        },
+    ];
+
+    let mut out = Vec::new();
+    receipts::export_csv(&mut out, &[cached, guarded]).unwrap();
+    let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "request_id,created,model,tier,node,prompt_tokens,completion_tokens,total_tokens,\
+             cached,coalesced,cancelled,charge_microusdc,policy_prompts,signer,signature",
+            "a,100,AURIA:STANDARD,STANDARD,http://127.0.0.1:8080/,4,3,7,true,,,12,,,",
+            "b,200,AURIA:STANDARD,STANDARD,http://127.0.0.1:8080/,4,3,7,,,,,one:aa;two:bb,,",
+        ]
+    );
+}
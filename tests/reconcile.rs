@@ -0,0 +1,58 @@
+// File: reconcile.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for token-count reconciliation and node flagging.
+//
+use auria::reconcile::{ReconcileConfig, Reconciler};
+
+fn reconciler() -> Reconciler {
+    Reconciler::new(ReconcileConfig {
+        tolerance: 0.10,
+        min_samples: 5,
+    })
+}
+
+#[test]
+fn bills_local_count_on_over_report() {
+    let r = reconciler();
+    let honest = r.record("n1", 10, 10);
+    assert_eq!(honest.billed, 10);
+    assert!(!honest.over_reported);
+
+    let within = r.record("n1", 11, 10);
+    assert_eq!(within.billed, 11);
+
+    let inflated = r.record("n1", 20, 10);
+    assert!(inflated.over_reported);
+    assert_eq!(inflated.billed, 10);
+}
+
+#[test]
+fn flags_consistent_over_reporters_only() {
+    let r = reconciler();
+    for _ in 0..10 {
+        r.record("honest", 100, 100);
+        r.record("greedy", 200, 100);
+    }
+    assert!(!r.is_flagged("honest"));
+    assert!(r.is_flagged("greedy"));
+
+    let report = r.report();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].node, "greedy");
+    assert_eq!(report[0].over_reports, 10);
+}
+
+#[test]
+fn estimates_never_reduce_billing_or_flag() {
+    let r = reconciler();
+    for _ in 0..10 {
+        let estimated = r.record_estimate("cjk", 200, 100);
+        assert!(estimated.over_reported);
+        assert_eq!(estimated.billed, 200);
+    }
+    assert!(!r.is_flagged("cjk"));
+
+    let report = r.report();
+    assert_eq!((report[0].samples, report[0].exact_samples), (10, 0));
+}
@@ -0,0 +1,181 @@
+// File: routing.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for node selection: tier and health fallbacks and per-node
+//     concurrency limits against mock nodes advertising different
+//     capabilities.
+//
+mod common;
+
+use std::{collections::BTreeMap, time::Duration};
+
+use auria::{
+    config::AppConfig,
+    models::{CompletionRequest, Tier},
+    AuriaAgent,
+};
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+/// A node answering health checks with `health` and advertising `caps`;
+/// batches (if advertised) take 200ms.
+async fn mock_node(health: StatusCode, caps: Value) -> String {
+    let app = Router::new()
+        .route("/healthz", get(move || async move { health }))
+        .route("/v1/capabilities", get(move || async move { Json(caps) }))
+        .route(
+            "/v1/generate/batch",
+            post(|Json(batch): Json<Value>| async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let n = batch["requests"].as_array().unwrap().len();
+                Json(json!({ "results": vec![json!({ "tokens": ["x"], "tokens_generated": 1 }); n] }))
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+fn completion(model: &str) -> CompletionRequest {
+    serde_json::from_value(json!({ "model": model, "prompt": "hello" })).unwrap()
+}
+
+#[tokio::test]
+async fn falls_back_to_unhealthy_nodes_of_the_tier_only() {
+    let pro_only = mock_node(StatusCode::OK, json!({ "tiers": ["PRO"] })).await;
+    // Down since the last check, with its earlier default capabilities.
+    let down = mock_node(StatusCode::SERVICE_UNAVAILABLE, json!({})).await;
+    let dir = std::env::temp_dir().join(format!("auria-routing-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls: vec![pro_only.clone(), down.clone()],
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    for _ in 0..4 {
+        agent.completions(completion("AURIA:NANO")).await.unwrap();
+    }
+    agent.completions(completion("AURIA:PRO")).await.unwrap();
+    let nodes: Vec<String> = agent
+        .ledger()
+        .unwrap()
+        .receipts()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.node)
+        .collect();
+    assert_eq!(
+        nodes,
+        [down.clone(), down.clone(), down.clone(), down, pro_only]
+    );
+}
+
+#[tokio::test]
+async fn waits_for_a_slot_on_a_busy_healthy_node() {
+    let busy = mock_node(StatusCode::OK, json!({ "batch_generate": true })).await;
+    let down = mock_node(StatusCode::SERVICE_UNAVAILABLE, json!({})).await;
+    let dir = std::env::temp_dir().join(format!("auria-routing-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls: vec![busy.clone(), down.clone()],
+        node_concurrency_limits: BTreeMap::from([(busy.clone(), 1)]),
+        batch_window_ms: 1,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    let nano = || agent.completions(completion("AURIA:NANO"));
+    let (a, b) = futures::future::join(nano(), nano()).await;
+    assert!(a.is_ok() && b.is_ok());
+    let nodes: Vec<String> = agent
+        .ledger()
+        .unwrap()
+        .receipts()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.node)
+        .collect();
+    assert_eq!(nodes, [busy.clone(), busy]);
+
+    let unknown = AppConfig {
+        node_concurrency_limits: BTreeMap::from([("http://elsewhere/".to_string(), 1)]),
+        ..cfg
+    };
+    assert!(AuriaAgent::new(unknown).await.is_err());
+}
+
+/// A batching node answering every request with one "x" token, reported
+/// as `reported` tokens.
+async fn reporting_node(reported: u32) -> String {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "batch_generate": true })) }),
+        )
+        .route(
+            "/v1/generate/batch",
+            post(move |Json(batch): Json<Value>| async move {
+                let n = batch["requests"].as_array().unwrap().len();
+                let result = json!({ "tokens": ["x"], "tokens_generated": reported });
+                Json(json!({ "results": vec![result; n] }))
+            }),
+        );
+    common::spawn_mock_node(app).await
+}
+
+#[tokio::test]
+async fn routes_to_flagged_nodes_only_when_nothing_else_qualifies() {
+    let honest = reporting_node(1).await;
+    let greedy = reporting_node(50).await;
+    // An exact vocabulary, so over-reports count towards flagging.
+    let ranks =
+        std::env::temp_dir().join(format!("auria-routing-{}.tiktoken", uuid::Uuid::new_v4()));
+    std::fs::write(&ranks, "eA== 0\n").unwrap();
+    let dir = std::env::temp_dir().join(format!("auria-routing-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls: vec![honest.clone(), greedy.clone()],
+        tokenizer_files: BTreeMap::from([(Tier::Nano, ranks.to_string_lossy().into_owned())]),
+        reconcile_min_samples: 1,
+        batch_window_ms: 1,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    for _ in 0..6 {
+        agent.completions(completion("AURIA:NANO")).await.unwrap();
+    }
+    let nodes: Vec<String> = agent
+        .ledger()
+        .unwrap()
+        .receipts()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.node)
+        .collect();
+    assert_eq!(nodes.iter().filter(|n| **n == greedy).count(), 1);
+    let nano = |a: &AuriaAgent| a.model("AURIA:NANO").unwrap().healthy_nodes;
+    assert_eq!(nano(&agent), 1);
+
+    // With every node of the tier flagged, requests still go through,
+    // billed at the local count.
+    let only_greedy = AppConfig {
+        node_urls: vec![greedy],
+        receipts_dir: Some(format!("{}-greedy", dir.to_string_lossy())),
+        ..cfg
+    };
+    let agent = AuriaAgent::new(only_greedy).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    for _ in 0..3 {
+        let resp = agent.completions(completion("AURIA:NANO")).await.unwrap();
+        assert_eq!(resp.usage.completion_tokens, 1);
+    }
+    assert_eq!(nano(&agent), 0);
+}
@@ -0,0 +1,84 @@
+// File: sampling.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for sampling parameter validation and capability-based
+//     rejection of unsupported parameters.
+//
+mod common;
+
+use auria::{
+    config::AppConfig,
+    error::AgentError,
+    models::{ChatCompletionRequest, Tier},
+    policy::PolicyEngine,
+    AuriaAgent,
+};
+use axum::{routing::get, Json, Router};
+use serde_json::json;
+
+fn chat(extra: serde_json::Value) -> ChatCompletionRequest {
+    let mut req =
+        json!({ "model": "AURIA:NANO", "messages": [{ "role": "user", "content": "hi" }] });
+    req.as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(req).unwrap()
+}
+
+#[test]
+fn validates_ranges() {
+    let policy = PolicyEngine {
+        default_tier: Tier::Standard,
+        max_cost_microusdc: 0,
+        system_prompts: Vec::new(),
+    };
+    let ok = chat(
+        json!({ "temperature": 0.7, "top_p": 1.0, "stop": ["\n"], "logit_bias": { "50256": -100 } }),
+    );
+    assert!(policy.validate_sampling(&ok.sampling).is_ok());
+
+    for bad in [
+        json!({ "temperature": 2.5 }),
+        json!({ "frequency_penalty": -3 }),
+        json!({ "stop": ["a", "b", "c", "d", "e"] }),
+        json!({ "logit_bias": { "hello": 1 } }),
+    ] {
+        assert!(
+            policy
+                .validate_sampling(&chat(bad.clone()).sampling)
+                .is_err(),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[tokio::test]
+async fn rejects_parameters_no_node_supports() {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route(
+            "/v1/capabilities",
+            get(|| async { Json(json!({ "sampling": ["temperature", "seed"] })) }),
+        );
+    let node = common::spawn_mock_node(app).await;
+
+    let cfg = AppConfig {
+        node_urls: vec![node],
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+    agent.check_nodes().await.unwrap();
+
+    assert!(agent
+        .chat_completions(chat(json!({ "temperature": 0.2, "seed": 7 })))
+        .await
+        .is_ok());
+    match agent
+        .chat_completions(chat(json!({ "logit_bias": { "1": 5 } })))
+        .await
+    {
+        Err(AgentError::InvalidRequest(msg)) => assert!(msg.contains("logit_bias"), "{}", msg),
+        other => panic!("expected invalid_request, got {:?}", other.map(|r| r.id)),
+    }
+}
@@ -0,0 +1,150 @@
+// File: shutdown.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for graceful shutdown: draining in-flight requests, failing
+//     readiness, and cancelling what outlives the grace period.
+//
+mod common;
+
+use std::time::Duration;
+
+use auria::{api, config::AppConfig, AuriaAgent};
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, sync::oneshot, task::JoinHandle};
+
+/// A batching node that answers after `delay` and reports two tokens
+/// produced for any cancelled request.
+async fn mock_node(delay: Duration) -> String {
+    let app = Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/v1/capabilities", get(|| async { Json(json!({ "batch_generate": true, "cancel": true })) }))
+        .route(
+            "/v1/generate/batch",
+            post(move |Json(batch): Json<Value>| async move {
+                tokio::time::sleep(delay).await;
+                let n = batch["requests"].as_array().unwrap().len();
+                Json(json!({ "results": vec![json!({ "tokens": ["done"], "tokens_generated": 1 }); n] }))
+            }),
+        )
+        .route("/v1/generate/cancel", post(|| async { Json(json!({ "tokens_generated": 2 })) }));
+    common::spawn_mock_node(app).await
+}
+
+struct Server {
+    url: String,
+    agent: AuriaAgent,
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<anyhow::Result<()>>,
+}
+
+async fn start(node_delay: Duration, grace_secs: u64) -> Server {
+    let dir = std::env::temp_dir().join(format!("auria-shutdown-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        node_urls: vec![mock_node(node_delay).await],
+        batch_window_ms: 1,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        shutdown_grace_secs: grace_secs,
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg.clone()).await.unwrap();
+    agent.check_nodes().await.unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (stop, stopped) = oneshot::channel();
+    let task = tokio::spawn(api::serve_with_shutdown(
+        listener,
+        cfg,
+        agent.clone(),
+        async move {
+            let _ = stopped.await;
+        },
+    ));
+    Server {
+        url,
+        agent,
+        stop,
+        task,
+    }
+}
+
+fn complete(url: &str) -> JoinHandle<reqwest::Result<reqwest::Response>> {
+    let req = reqwest::Client::new()
+        .post(format!("{}/v1/completions", url))
+        .json(&json!({ "model": "AURIA:NANO", "prompt": "hello" }));
+    tokio::spawn(req.send())
+}
+
+#[tokio::test]
+async fn drains_in_flight_requests_and_fails_readiness() {
+    let server = start(Duration::from_millis(400), 30).await;
+    let http = reqwest::Client::new();
+    assert_eq!(
+        http.get(format!("{}/readyz", server.url))
+            .send()
+            .await
+            .unwrap()
+            .status(),
+        200
+    );
+
+    let in_flight = complete(&server.url);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    server.stop.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let ready = http
+        .get(format!("{}/readyz", server.url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(ready.status(), 503);
+    let refused = complete(&server.url).await.unwrap().unwrap();
+    assert_eq!(refused.status(), 503);
+    assert!(refused.headers().contains_key("retry-after"));
+
+    let resp = in_flight.await.unwrap().unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.json::<Value>().await.unwrap()["choices"][0]["text"],
+        "done"
+    );
+    tokio::time::timeout(Duration::from_secs(2), server.task)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(server.agent.ledger().unwrap().receipts().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn cancels_requests_that_outlive_the_grace_period() {
+    let server = start(Duration::from_secs(10), 1).await;
+
+    let in_flight = complete(&server.url);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    server.stop.send(()).unwrap();
+    tokio::time::timeout(Duration::from_secs(3), server.task)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let resp = in_flight.await.unwrap().unwrap();
+    assert_eq!(resp.status(), 503);
+    assert!(resp
+        .text()
+        .await
+        .unwrap()
+        .contains("shut down before the request finished"));
+
+    // The cancellation receipt is written before serve returns.
+    let receipts = server.agent.ledger().unwrap().receipts().unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        (receipts[0].cancelled, receipts[0].completion_tokens),
+        (Some(true), 2)
+    );
+}
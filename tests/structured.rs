@@ -0,0 +1,110 @@
+// File: structured.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for response_format: JSON repair, schema validation and
+//     the agent's retry-then-fail behaviour.
+//
+use std::collections::BTreeMap;
+
+use auria::{
+    config::{AppConfig, TierSpec},
+    error::AgentError,
+    models::{ChatCompletionRequest, ResponseFormat, Tier},
+    structured::{self, OutputSchema},
+    AuriaAgent,
+};
+use serde_json::json;
+
+fn chat(content: &str, format: serde_json::Value) -> ChatCompletionRequest {
+    serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "messages": [{ "role": "user", "content": content }],
+        "response_format": format,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn repairs_and_validates() {
+    assert_eq!(
+        structured::repair("```json\n{\"a\": 1}\n```").unwrap(),
+        json!({ "a": 1 })
+    );
+    assert_eq!(
+        structured::repair("Sure! {\"a\": [1]} Hope it helps.").unwrap(),
+        json!({ "a": [1] })
+    );
+    assert!(structured::repair("no json here").is_none());
+
+    let format: ResponseFormat = serde_json::from_value(json!({
+        "type": "json_schema",
+        "json_schema": { "name": "point", "schema": { "type": "object", "required": ["x"] } },
+    }))
+    .unwrap();
+    let schema = OutputSchema::from_format(&format).unwrap().unwrap();
+    assert_eq!(schema.check("{\"x\": 1}").unwrap(), "{\"x\":1}");
+    assert!(schema.check("{\"y\": 1}").unwrap_err().contains("point"));
+}
+
+#[tokio::test]
+async fn returns_valid_json_or_fails_clearly() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+
+    // The stub node echoes the prompt; repair extracts the user's object.
+    let ok = agent
+        .chat_completions(chat("{\"a\": 1}", json!({ "type": "json_object" })))
+        .await
+        .unwrap();
+    let content = ok.choices[0]
+        .message
+        .content
+        .as_ref()
+        .and_then(|c| c.as_text());
+    assert_eq!(content, Some("{\"a\":1}"));
+
+    let schema = json!({ "type": "json_schema", "json_schema": { "name": "p", "schema": { "required": ["x"] } } });
+    match agent.chat_completions(chat("{\"y\": 1}", schema)).await {
+        Err(AgentError::UpstreamError(msg)) => {
+            assert!(msg.contains("after 3 attempt(s)"), "{}", msg)
+        }
+        other => panic!("expected upstream_error, got {:?}", other.map(|r| r.id)),
+    }
+}
+
+#[tokio::test]
+async fn stops_retrying_when_the_budget_is_spent() {
+    // One micro-USDC per token. The first attempt's worst case is 135 and
+    // the retries' 202 and 269: each fits the limit alone, but not on top of
+    // what earlier attempts already cost.
+    let spec = TierSpec {
+        price_microusdc_per_1k: 1000,
+        ..AppConfig::default().tier_spec(Tier::Nano)
+    };
+    let dir = std::env::temp_dir().join(format!("auria-structured-{}", uuid::Uuid::new_v4()));
+    let cfg = AppConfig {
+        tier_specs: BTreeMap::from([(Tier::Nano, spec)]),
+        max_cost_microusdc: 300,
+        receipts_dir: Some(dir.to_string_lossy().into_owned()),
+        ..AppConfig::default()
+    };
+    let agent = AuriaAgent::new(cfg).await.unwrap();
+
+    let schema = json!({ "type": "json_schema", "json_schema": { "name": "p", "schema": { "required": ["x"] } } });
+    let req = ChatCompletionRequest {
+        max_tokens: Some(100),
+        ..chat("{\"y\": 1}", schema)
+    };
+    match agent.chat_completions(req).await {
+        Err(AgentError::UpstreamError(msg)) => {
+            assert!(msg.contains("after 2 attempt(s)"), "{}", msg);
+            assert!(msg.contains("no budget for another"), "{}", msg);
+        }
+        other => panic!("expected upstream_error, got {:?}", other.map(|r| r.id)),
+    }
+
+    let receipts = agent.ledger().unwrap().receipts().unwrap();
+    assert_eq!(receipts.len(), 2);
+    let charged: u64 = receipts.iter().map(|r| r.charge_microusdc.unwrap()).sum();
+    assert!(charged <= 300, "{}", charged);
+    let _ = std::fs::remove_dir_all(dir);
+}
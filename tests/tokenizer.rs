@@ -0,0 +1,61 @@
+// File: tokenizer.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for built-in tokenizers, tiktoken rank file loading and
+//     per-tier tokenizer selection.
+//
+use std::collections::BTreeMap;
+
+use auria::models::Tier;
+use auria::tokenizer::{self, BpeTokenizer, Tokenizer, TokenizerRegistry};
+use base64::Engine;
+
+fn rank_file(tokens: &[&str]) -> std::path::PathBuf {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let body: String = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, t)| format!("{} {}\n", b64.encode(t), i))
+        .collect();
+    let path = std::env::temp_dir().join(format!("auria-tok-{}.tiktoken", uuid::Uuid::new_v4()));
+    std::fs::write(&path, body).unwrap();
+    path
+}
+
+#[test]
+fn builtin_tokenizers() {
+    assert_eq!(tokenizer::builtin("approx").unwrap().count("abcdefgh!"), 3);
+    assert_eq!(
+        tokenizer::builtin("whitespace")
+            .unwrap()
+            .count("Hello, Auria node."),
+        5
+    );
+    assert!(tokenizer::builtin("nope").is_err());
+}
+
+#[test]
+fn bpe_merges_by_rank() {
+    let path = rank_file(&["a", "b", " ", "ab", "abab", " ab"]);
+    let tok = BpeTokenizer::from_file(&path, tokenizer::CL100K_PATTERN).unwrap();
+    assert_eq!(tok.count("abab"), 1);
+    assert_eq!(tok.count("ababa"), 2);
+    assert_eq!(tok.count("ab ab"), 2);
+    assert_eq!(tok.count(""), 0);
+}
+
+#[test]
+fn registry_selects_per_tier() {
+    let mut files = BTreeMap::new();
+    files.insert(
+        Tier::Pro,
+        rank_file(&["x", "y", "xy"]).display().to_string(),
+    );
+    let reg = TokenizerRegistry::load("approx", &files, None).unwrap();
+
+    assert_eq!(reg.for_tier(Tier::Pro).count("xyxy"), 2);
+    assert_eq!(reg.for_tier(Tier::Standard).name(), "approx");
+
+    files.insert(Tier::Max, "/nonexistent/tokenizer.tiktoken".to_string());
+    assert!(TokenizerRegistry::load("approx", &files, None).is_err());
+}
@@ -0,0 +1,121 @@
+// File: tools.rs - This file is part of AURIA
+// Copyright (c) 2026 AURIA Developers and Contributors
+// Description:
+//     Tests for tool calling: tool_choice validation, parsing of tool
+//     calls from node output, and tool call chunks when streaming.
+//
+use auria::{
+    config::AppConfig,
+    error::AgentError,
+    models::{ChatCompletionRequest, Tool},
+    tools::{self, ToolMode},
+    AuriaAgent,
+};
+use serde_json::json;
+
+fn weather_tool() -> Tool {
+    tool("get_weather")
+}
+
+fn tool(name: &str) -> Tool {
+    serde_json::from_value(json!({
+        "type": "function",
+        "function": { "name": name, "parameters": { "type": "object" } },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn parses_calls_and_validates_choice() {
+    let tools = [weather_tool()];
+    let text = r#"Checking. <tool_call>{"name": "get_weather", "arguments": {"city": "Paris"}}</tool_call>
+<tool_call>{"name": "unknown"}</tool_call>"#;
+    let (content, calls) = tools::parse_tool_calls(text, &tools);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function.arguments, r#"{"city":"Paris"}"#);
+    assert_eq!(
+        content.unwrap(),
+        "Checking. \n<tool_call>{\"name\": \"unknown\"}</tool_call>"
+    );
+
+    let named =
+        serde_json::from_value(json!({ "type": "function", "function": { "name": "nope" } }))
+            .unwrap();
+    assert!(ToolMode::resolve(&tools, Some(&named)).is_err());
+    assert_eq!(ToolMode::resolve(&tools, None).unwrap(), ToolMode::Auto);
+    assert!(ToolMode::resolve(
+        &[],
+        Some(&serde_json::from_value(json!("required")).unwrap())
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn returns_structured_tool_calls() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    // The stub node echoes the prompt, so the user turn doubles as node output.
+    let req: ChatCompletionRequest = serde_json::from_value(json!({
+        "model": "AURIA:NANO",
+        "messages": [{
+            "role": "user",
+            "content": "<tool_call>{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Oslo\"}}</tool_call>",
+        }],
+        "tools": [weather_tool()],
+    }))
+    .unwrap();
+
+    let resp = agent.chat_completions(req.clone()).await.unwrap();
+    assert_eq!(resp.choices[0].finish_reason, "tool_calls");
+    let calls = resp.choices[0].message.tool_calls.as_ref().unwrap();
+    assert_eq!(calls[0].function.name, "get_weather");
+
+    let chunks = agent.chat_completion_chunks(req).await.unwrap();
+    let last = chunks.last().unwrap();
+    assert_eq!(last.choices[0].finish_reason.as_deref(), Some("tool_calls"));
+    assert!(chunks
+        .iter()
+        .any(|c| c.choices[0].delta.tool_calls.is_some()));
+}
+
+#[tokio::test]
+async fn enforces_required_and_named_tool_choice() {
+    let agent = AuriaAgent::new(AppConfig::default()).await.unwrap();
+    let call = "<tool_call>{\"name\": \"get_weather\", \"arguments\": {}}</tool_call>";
+    let chat = |content: &str, choice: serde_json::Value| -> ChatCompletionRequest {
+        serde_json::from_value(json!({
+            "model": "AURIA:NANO",
+            "messages": [{ "role": "user", "content": content }],
+            "tools": [weather_tool(), tool("get_time")],
+            "tool_choice": choice,
+        }))
+        .unwrap()
+    };
+    let named = |name: &str| json!({ "type": "function", "function": { "name": name } });
+
+    assert!(agent
+        .chat_completions(chat(call, json!("required")))
+        .await
+        .is_ok());
+    assert!(agent
+        .chat_completions(chat(call, named("get_weather")))
+        .await
+        .is_ok());
+
+    let rejected = [
+        (
+            chat("It is sunny.", json!("required")),
+            "no tool was called",
+        ),
+        (
+            chat("It is sunny.", named("get_weather")),
+            "no tool was called",
+        ),
+        (chat(call, named("get_time")), "get_weather was called"),
+    ];
+    for (req, reason) in rejected {
+        match agent.chat_completions(req).await {
+            Err(AgentError::UpstreamError(msg)) => assert!(msg.contains(reason), "{}", msg),
+            other => panic!("expected upstream_error, got {:?}", other.map(|r| r.id)),
+        }
+    }
+}